@@ -0,0 +1,75 @@
+//! Compares the scalar search of `LinearMap` with the SIMD search of `IntLinearMap` and the
+//! fingerprint search of `FingerprintLinearMap`.
+//!
+//! Run with `cargo bench --bench search`.
+
+extern crate linear_map;
+
+use std::fmt::Debug;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use linear_map::{FingerprintLinearMap, IntKey, IntLinearMap, LinearMap};
+
+const LOOKUPS: usize = 1 << 22;
+
+/// Returns the average time of a lookup of `keys`, cycling through them.
+fn measure<F: FnMut(usize) -> bool>(len: usize, mut lookup: F) -> Duration {
+    let mut found = 0;
+    // Warm up.
+    for i in 0..LOOKUPS / 16 {
+        found += lookup(i % len) as usize;
+    }
+    let start = Instant::now();
+    for i in 0..LOOKUPS {
+        found += lookup(i % len) as usize;
+    }
+    let elapsed = start.elapsed();
+    black_box(found);
+    elapsed / LOOKUPS as u32
+}
+
+fn bench<K, F>(name: &str, to_key: F)
+    where
+        K: IntKey + Debug,
+        F: Fn(usize) -> K,
+{
+    println!("{}", name);
+    println!("{:>6} {:>12} {:>12} {:>8}", "len", "LinearMap", "IntLinearMap", "speedup");
+    for &len in &[4, 8, 16, 32, 64, 128, 256] {
+        let keys: Vec<K> = (0..len).map(&to_key).collect();
+        let linear: LinearMap<K, usize> = keys.iter().cloned().zip(0..).collect();
+        let int: IntLinearMap<K, usize> = linear.clone().into();
+
+        let scalar = measure(len, |i| black_box(&linear).get(black_box(&keys[i])).is_some());
+        let simd = measure(len, |i| black_box(&int).get(black_box(&keys[i])).is_some());
+        println!("{:>6} {:>10.1?} {:>12.1?} {:>7.2}x",
+                 len, scalar, simd, scalar.as_secs_f64() / simd.as_secs_f64());
+    }
+    println!();
+}
+
+fn bench_strings() {
+    println!("String with a common prefix");
+    println!("{:>6} {:>12} {:>12} {:>8}", "len", "LinearMap", "Fingerprint", "speedup");
+    for &len in &[4, 8, 16, 32, 64, 128, 256] {
+        let keys: Vec<String> = (0..len).map(|i| format!("https://example.com/some/path/{}", i)).collect();
+        let linear: LinearMap<String, usize> = keys.iter().cloned().zip(0..).collect();
+        let fingerprint: FingerprintLinearMap<String, usize> = linear.clone().into();
+
+        let scalar = measure(len, |i| black_box(&linear).get(black_box(&keys[i])).is_some());
+        let hashed = measure(len, |i| black_box(&fingerprint).get(black_box(&keys[i])).is_some());
+        println!("{:>6} {:>10.1?} {:>12.1?} {:>7.2}x",
+                 len, scalar, hashed, scalar.as_secs_f64() / hashed.as_secs_f64());
+    }
+    println!();
+}
+
+fn main() {
+    bench("u8", |i| i as u8);
+    bench("u16", |i| (i * 7) as u16);
+    bench("u32", |i| (i * 7919) as u32);
+    bench("u64", |i| (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    bench("char", |i| std::char::from_u32(0x4E00 + i as u32).unwrap());
+    bench_strings();
+}
@@ -0,0 +1,658 @@
+use core::borrow::Borrow;
+use core::cmp;
+use core::fmt;
+use core::hash::Hash;
+use core::iter::{FromIterator, FusedIterator};
+use core::mem;
+use core::ops::Index;
+use std::collections::hash_map::{self, HashMap};
+
+use {IntoIter as LinearIntoIter, Iter as LinearIter, IterMut as LinearIterMut, LinearMap};
+
+/// The number of entries above which a map created by `AdaptiveMap::new` switches to hashing.
+pub const DEFAULT_THRESHOLD: usize = 16;
+
+/// AdaptiveMap is a map that stores its entries in a [`LinearMap`](struct.LinearMap.html)
+/// while it is small and moves them into a [`HashMap`] once it grows past a threshold. This
+/// avoids the O(n) cost of the linear search for maps whose size is not known in advance,
+/// while keeping the advantages of LinearMap for the common case of few entries.
+///
+/// The entries are moved into a `HashMap` when an insert makes the map hold more than
+/// `threshold` entries. They are moved back into a `LinearMap` when a removal makes the map
+/// hold less than half of `threshold` entries, so that alternating inserts and removals
+/// around the threshold do not move the entries every time. With a threshold below 2, this
+/// happens when the map becomes empty.
+///
+/// While the entries are stored in a `LinearMap`, the
+/// [ordering](struct.LinearMap.html#ordering) of LinearMap applies. Moving the entries into
+/// a `HashMap` loses the order.
+///
+/// [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
+///
+/// # Examples
+///
+/// ```
+/// use linear_map::AdaptiveMap;
+///
+/// let mut map = AdaptiveMap::with_threshold(4);
+/// for i in 0..4 {
+///     map.insert(i, i * 10);
+/// }
+/// assert!(!map.is_hashed());
+///
+/// map.insert(4, 40);
+/// assert!(map.is_hashed());
+///
+/// for i in 1..5 {
+///     map.remove(&i);
+/// }
+/// assert!(!map.is_hashed());
+/// assert_eq!(map.get(&0), Some(&0));
+/// ```
+#[derive(Clone)]
+pub struct AdaptiveMap<K, V>
+    where K: Hash + Eq
+{
+    storage: Storage<K, V>,
+    threshold: usize,
+}
+
+#[derive(Clone)]
+enum Storage<K, V>
+    where K: Hash + Eq
+{
+    Linear(LinearMap<K, V>),
+    Hashed(HashMap<K, V>),
+}
+
+impl<K, V> AdaptiveMap<K, V>
+    where K: Hash + Eq
+{
+    /// Creates an empty `AdaptiveMap` which switches to hashing above
+    /// [`DEFAULT_THRESHOLD`](adaptive/constant.DEFAULT_THRESHOLD.html) entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::AdaptiveMap;
+    ///
+    /// let map: AdaptiveMap<usize, &str> = AdaptiveMap::new();
+    /// ```
+    pub fn new() -> Self {
+        AdaptiveMap::with_threshold(DEFAULT_THRESHOLD)
+    }
+
+    /// Creates an empty `AdaptiveMap` which switches to hashing above `threshold` entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::AdaptiveMap;
+    ///
+    /// let map: AdaptiveMap<usize, &str> = AdaptiveMap::with_threshold(32);
+    /// assert_eq!(map.threshold(), 32);
+    /// ```
+    pub fn with_threshold(threshold: usize) -> Self {
+        AdaptiveMap {
+            storage: Storage::Linear(LinearMap::new()),
+            threshold,
+        }
+    }
+
+    /// Returns the number of entries above which the map switches to hashing.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns `true` if the entries are currently stored in a `HashMap`.
+    pub fn is_hashed(&self) -> bool {
+        match self.storage {
+            Storage::Linear(_) => false,
+            Storage::Hashed(_) => true,
+        }
+    }
+
+    /// Clears the map, removing all key-value pairs. The entries are stored in a `LinearMap`
+    /// afterwards.
+    pub fn clear(&mut self) {
+        match self.storage {
+            Storage::Linear(ref mut map) => map.clear(),
+            Storage::Hashed(_) => self.storage = Storage::Linear(LinearMap::new()),
+        }
+    }
+
+    /// Returns a reference to the requested value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map, but at most O(threshold).
+    /// O(1) on average when the map is hashed.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+    {
+        match self.storage {
+            Storage::Linear(ref map) => map.get(key),
+            Storage::Hashed(ref map) => map.get(key),
+        }
+    }
+
+    /// Returns a mutable reference to the requested value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map, but at most O(threshold).
+    /// O(1) on average when the map is hashed.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+    {
+        match self.storage {
+            Storage::Linear(ref mut map) => map.get_mut(key),
+            Storage::Hashed(ref mut map) => map.get_mut(key),
+        }
+    }
+
+    /// Returns a tuple with references to the requested key and value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map, but at most O(threshold).
+    /// O(1) on average when the map is hashed.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+    {
+        match self.storage {
+            Storage::Linear(ref map) => map.get_key_value(key),
+            Storage::Hashed(ref map) => map.get_key_value(key),
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map. If the map holds more than `threshold`
+    /// entries afterwards, the entries are moved into a `HashMap`.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old value
+    /// is returned. The key is not updated, though.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::AdaptiveMap;
+    ///
+    /// let mut map = AdaptiveMap::with_threshold(1);
+    /// assert_eq!(map.insert(0, "a"), None);
+    /// assert_eq!(map.insert(0, "b"), Some("a"));
+    /// assert!(!map.is_hashed());
+    /// assert_eq!(map.insert(1, "c"), None);
+    /// assert!(map.is_hashed());
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old = match self.storage {
+            Storage::Linear(ref mut map) => map.insert(key, value),
+            Storage::Hashed(ref mut map) => return map.insert(key, value),
+        };
+        if old.is_none() && self.len() > self.threshold {
+            self.promote();
+        }
+        old
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        match self.storage {
+            Storage::Linear(ref map) => map.len(),
+            Storage::Hashed(ref map) => map.len(),
+        }
+    }
+
+    /// Returns if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the entry from the map and returns its value. If the map holds less than
+    /// half of `threshold` entries afterwards, the entries are moved into a `LinearMap`.
+    ///
+    /// While the entries are stored in a `LinearMap`, this is equivalent to
+    /// [`LinearMap::remove`](struct.LinearMap.html#method.remove).
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the entry from the map and returns the stored key and value. If the map
+    /// holds less than half of `threshold` entries afterwards, the entries are moved into
+    /// a `LinearMap`.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+    {
+        let entry = match self.storage {
+            Storage::Linear(ref mut map) => return map.swap_remove_entry(key),
+            Storage::Hashed(ref mut map) => map.remove_entry(key),
+        };
+        // A threshold below 2 would never demote, so an empty map is always demoted.
+        if self.len() < cmp::max(self.threshold / 2, 1) {
+            self.demote();
+        }
+        entry
+    }
+
+    /// Gets an iterator over the keys of the map.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { iter: self.iter() }
+    }
+
+    /// Gets an iterator over the values of the map.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { iter: self.iter() }
+    }
+
+    /// Gets a mutable iterator over the values of the map.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut { iter: self.iter_mut() }
+    }
+
+    /// Gets an iterator over the entries of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::AdaptiveMap;
+    ///
+    /// let mut map = AdaptiveMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    ///
+    /// for (key, value) in map.iter() {
+    ///     println!("{}: {}", key, value);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let inner = match self.storage {
+            Storage::Linear(ref map) => IterInner::Linear(map.iter()),
+            Storage::Hashed(ref map) => IterInner::Hashed(map.iter()),
+        };
+        Iter { inner }
+    }
+
+    /// Gets a mutable iterator over the entries of the map.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        let inner = match self.storage {
+            Storage::Linear(ref mut map) => IterMutInner::Linear(map.iter_mut()),
+            Storage::Hashed(ref mut map) => IterMutInner::Hashed(map.iter_mut()),
+        };
+        IterMut { inner }
+    }
+
+    fn promote(&mut self) {
+        if let Storage::Linear(ref mut map) = self.storage {
+            let mut hashed = HashMap::with_capacity(map.len());
+            hashed.extend(mem::take(map));
+            self.storage = Storage::Hashed(hashed);
+        }
+    }
+
+    fn demote(&mut self) {
+        if let Storage::Hashed(ref mut map) = self.storage {
+            let mut linear = LinearMap::with_capacity(self.threshold);
+            for (key, value) in mem::take(map) {
+                // The keys of the `HashMap` are unique, so there is no need to search.
+                linear.keys.push(key);
+                linear.values.push(value);
+            }
+            self.storage = Storage::Linear(linear);
+        }
+    }
+}
+
+impl<K, V> Default for AdaptiveMap<K, V>
+    where K: Hash + Eq
+{
+    /// Creates an empty `AdaptiveMap`.
+    fn default() -> Self {
+        AdaptiveMap::new()
+    }
+}
+
+impl<K, V> fmt::Debug for AdaptiveMap<K, V>
+    where
+        K: Hash + Eq + fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Whether either map has moved its entries into a `HashMap` does not affect equality.
+impl<K, V> PartialEq for AdaptiveMap<K, V>
+    where
+        K: Hash + Eq,
+        V: PartialEq,
+{
+    fn eq(&self, other: &AdaptiveMap<K, V>) -> bool {
+        self.len() == other.len() &&
+            self.iter().all(|(key, value)| other.get(key).is_some_and(|v| *value == *v))
+    }
+}
+
+impl<K, V> Eq for AdaptiveMap<K, V>
+    where
+        K: Hash + Eq,
+        V: Eq,
+{
+}
+
+impl<K, V> FromIterator<(K, V)> for AdaptiveMap<K, V>
+    where K: Hash + Eq
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> AdaptiveMap<K, V> {
+        let mut map = AdaptiveMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+/// Inserts all entries of the iterator. Entries with a key that is already present replace
+/// the value in the map.
+impl<K, V> Extend<(K, V)> for AdaptiveMap<K, V>
+    where K: Hash + Eq
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> Extend<(&'a K, &'a V)> for AdaptiveMap<K, V>
+    where
+        K: Hash + Eq + Copy + 'a,
+        V: Copy + 'a,
+{
+    fn extend<T: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+impl<K, Q, V> Index<&Q> for AdaptiveMap<K, V>
+    where
+        K: Hash + Eq + Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+/// An iterator over the entries of an AdaptiveMap.
+///
+/// This struct is created by the `iter` method on [`AdaptiveMap`](struct.AdaptiveMap.html). See its documentation for more.
+pub struct Iter<'a, K: 'a, V: 'a> {
+    inner: IterInner<'a, K, V>,
+}
+
+enum IterInner<'a, K: 'a, V: 'a> {
+    Linear(LinearIter<'a, K, V>),
+    Hashed(hash_map::Iter<'a, K, V>),
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        match self.inner {
+            IterInner::Linear(ref mut iter) => iter.next(),
+            IterInner::Hashed(ref mut iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.inner {
+            IterInner::Linear(ref iter) => iter.size_hint(),
+            IterInner::Hashed(ref iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {
+    fn len(&self) -> usize {
+        match self.inner {
+            IterInner::Linear(ref iter) => iter.len(),
+            IterInner::Hashed(ref iter) => iter.len(),
+        }
+    }
+}
+
+impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
+
+impl<'a, K, V> IntoIterator for &'a AdaptiveMap<K, V>
+    where K: Hash + Eq
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+/// A mutable iterator over the entries of an AdaptiveMap.
+///
+/// This struct is created by the `iter_mut` method on [`AdaptiveMap`](struct.AdaptiveMap.html). See its documentation for more.
+pub struct IterMut<'a, K: 'a, V: 'a> {
+    inner: IterMutInner<'a, K, V>,
+}
+
+enum IterMutInner<'a, K: 'a, V: 'a> {
+    Linear(LinearIterMut<'a, K, V>),
+    Hashed(hash_map::IterMut<'a, K, V>),
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a mut V)> {
+        match self.inner {
+            IterMutInner::Linear(ref mut iter) => iter.next(),
+            IterMutInner::Hashed(ref mut iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.inner {
+            IterMutInner::Linear(ref iter) => iter.size_hint(),
+            IterMutInner::Hashed(ref iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {
+    fn len(&self) -> usize {
+        match self.inner {
+            IterMutInner::Linear(ref iter) => iter.len(),
+            IterMutInner::Hashed(ref iter) => iter.len(),
+        }
+    }
+}
+
+impl<'a, K, V> FusedIterator for IterMut<'a, K, V> {}
+
+impl<'a, K, V> IntoIterator for &'a mut AdaptiveMap<K, V>
+    where K: Hash + Eq
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the keys of an AdaptiveMap.
+///
+/// This struct is created by the `keys` method on [`AdaptiveMap`](struct.AdaptiveMap.html). See its documentation for more.
+pub struct Keys<'a, K: 'a, V: 'a> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+
+/// An iterator over the values of an AdaptiveMap.
+///
+/// This struct is created by the `values` method on [`AdaptiveMap`](struct.AdaptiveMap.html). See its documentation for more.
+pub struct Values<'a, K: 'a, V: 'a> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.iter.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
+
+/// A mutable iterator over the values of an AdaptiveMap.
+///
+/// This struct is created by the `values_mut` method on [`AdaptiveMap`](struct.AdaptiveMap.html). See its documentation for more.
+pub struct ValuesMut<'a, K: 'a, V: 'a> {
+    iter: IterMut<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<&'a mut V> {
+        self.iter.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for ValuesMut<'a, K, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, K, V> FusedIterator for ValuesMut<'a, K, V> {}
+
+/// An owning iterator over the entries of an AdaptiveMap.
+///
+/// This struct is created by the `into_iter` method on [`AdaptiveMap`](struct.AdaptiveMap.html). See its documentation for more.
+pub struct IntoIter<K, V> {
+    inner: IntoIterInner<K, V>,
+}
+
+enum IntoIterInner<K, V> {
+    Linear(LinearIntoIter<K, V>),
+    Hashed(hash_map::IntoIter<K, V>),
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        match self.inner {
+            IntoIterInner::Linear(ref mut iter) => iter.next(),
+            IntoIterInner::Hashed(ref mut iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.inner {
+            IntoIterInner::Linear(ref iter) => iter.size_hint(),
+            IntoIterInner::Hashed(ref iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+    fn len(&self) -> usize {
+        match self.inner {
+            IntoIterInner::Linear(ref iter) => iter.len(),
+            IntoIterInner::Hashed(ref iter) => iter.len(),
+        }
+    }
+}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+impl<K, V> IntoIterator for AdaptiveMap<K, V>
+    where K: Hash + Eq
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        let inner = match self.storage {
+            Storage::Linear(map) => IntoIterInner::Linear(map.into_iter()),
+            Storage::Hashed(map) => IntoIterInner::Hashed(map.into_iter()),
+        };
+        IntoIter { inner }
+    }
+}
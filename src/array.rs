@@ -0,0 +1,558 @@
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FromIterator;
+use core::mem;
+use core::ops::Index;
+
+use inline_vec::{self, InlineVec};
+use {find, Iter, IterMut, Keys, Values, ValuesMut};
+
+/// ArrayLinearMap is a map with a fixed capacity of `N` entries which are stored inline,
+/// so the map never allocates. Apart from that, it works like
+/// [`LinearMap`](struct.LinearMap.html) and provides the same methods, including the same
+/// [ordering](struct.LinearMap.html#ordering) guarantees.
+///
+/// Inserting a new key into a full map panics with [`insert`](#method.insert).
+/// [`try_insert`](#method.try_insert) returns the entry instead.
+///
+/// # Examples
+///
+/// ```
+/// use linear_map::ArrayLinearMap;
+///
+/// let mut map: ArrayLinearMap<&str, u32, 2> = ArrayLinearMap::new();
+///
+/// map.insert("a", 1);
+/// map.insert("b", 2);
+/// assert_eq!(map.get("a"), Some(&1));
+///
+/// // The map is full, so new keys are rejected.
+/// assert_eq!(map.try_insert("c", 3), Err(("c", 3)));
+///
+/// // Replacing the value of an existing key still works.
+/// assert_eq!(map.try_insert("b", 4), Ok(Some(2)));
+/// ```
+#[derive(Clone)]
+pub struct ArrayLinearMap<K, V, const N: usize>
+    where K: PartialEq
+{
+    keys: InlineVec<K, N>,
+    values: InlineVec<V, N>,
+}
+
+impl<K, V, const N: usize> ArrayLinearMap<K, V, N>
+    where K: PartialEq
+{
+    /// Creates an empty `ArrayLinearMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::ArrayLinearMap;
+    ///
+    /// let map: ArrayLinearMap<usize, &str, 8> = ArrayLinearMap::new();
+    /// ```
+    pub const fn new() -> Self {
+        ArrayLinearMap {
+            keys: InlineVec::new(),
+            values: InlineVec::new(),
+        }
+    }
+
+    /// Returns the number of elements the map can hold, which is always `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::ArrayLinearMap;
+    ///
+    /// assert_eq!(ArrayLinearMap::<i32, &str, 8>::new().capacity(), 8);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Clears the map, removing all key-value pairs.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.values.clear();
+    }
+
+    /// Returns a reference to the requested value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::ArrayLinearMap;
+    ///
+    /// let mut map: ArrayLinearMap<_, _, 4> = ArrayLinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// assert_eq!(map.get(&1), Some(&"b"));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.find(key).map(|i| &self.values.as_slice()[i])
+    }
+
+    /// Returns a mutable reference to the requested value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.find(key) {
+            Some(i) => Some(&mut self.values.as_mut_slice()[i]),
+            None => None,
+        }
+    }
+
+    /// Returns a tuple with references to the requested key and value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.find(key).map(|i| (&self.keys.as_slice()[i], &self.values.as_slice()[i]))
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old value
+    /// is returned. The key is not updated, though.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present and the map is full.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::ArrayLinearMap;
+    ///
+    /// let mut map: ArrayLinearMap<_, _, 4> = ArrayLinearMap::new();
+    /// assert_eq!(map.insert(0, "a"), None);
+    /// assert_eq!(map.insert(1, "b"), None);
+    /// assert_eq!(map.insert(1, "c"), Some("b"));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.try_insert(key, value) {
+            Ok(old) => old,
+            Err(_) => panic!("ArrayLinearMap is full (capacity is {})", N),
+        }
+    }
+
+    /// Inserts a key-value pair into the map, or returns it when the key is not present
+    /// and the map is full.
+    ///
+    /// On success, the old value of the key is returned like with
+    /// [`insert`](#method.insert).
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::ArrayLinearMap;
+    ///
+    /// let mut map: ArrayLinearMap<_, _, 1> = ArrayLinearMap::new();
+    /// assert_eq!(map.try_insert(0, "a"), Ok(None));
+    /// assert_eq!(map.try_insert(0, "b"), Ok(Some("a")));
+    /// assert_eq!(map.try_insert(1, "c"), Err((1, "c")));
+    /// ```
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)> {
+        if let Some(i) = self.find(&key) {
+            Ok(Some(mem::replace(&mut self.values.as_mut_slice()[i], value)))
+        } else if self.is_full() {
+            Err((key, value))
+        } else {
+            // Neither push can fail since both vectors have the same length.
+            let _ = self.keys.push(key);
+            let _ = self.values.push(value);
+            Ok(None)
+        }
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.keys.len() == 0
+    }
+
+    /// Returns if the map holds `N` elements, so that no new key can be inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::ArrayLinearMap;
+    ///
+    /// let mut map: ArrayLinearMap<_, _, 1> = ArrayLinearMap::new();
+    /// assert!(!map.is_full());
+    /// map.insert(0, "a");
+    /// assert!(map.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.keys.is_full()
+    }
+
+    /// Removes the entry from the map and returns its value. This is equivalent to
+    /// [`swap_remove`](#method.swap_remove).
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.swap_remove(key)
+    }
+
+    /// Removes the entry from the map and returns its value. The last entry of the map
+    /// takes the place of the removed one.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.swap_remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the entry from the map and returns the stored key and value. The last entry
+    /// of the map takes the place of the removed one.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn swap_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.find(key).map(|i| (self.keys.swap_remove(i), self.values.swap_remove(i)))
+    }
+
+    /// Removes the entry from the map and returns its value. All entries after the removed
+    /// one are moved forward, which preserves the order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::ArrayLinearMap;
+    ///
+    /// let mut map: ArrayLinearMap<_, _, 4> = ArrayLinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// map.insert(2, "c");
+    /// assert_eq!(map.shift_remove(&0), Some("a"));
+    /// assert!(map.keys().eq(&[1, 2]));
+    /// ```
+    pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.shift_remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the entry from the map and returns the stored key and value. All entries
+    /// after the removed one are moved forward, which preserves the order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn shift_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.find(key).map(|i| (self.keys.remove(i), self.values.remove(i)))
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Gets an iterator over the keys of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn keys(&self) -> Keys<'_, K> {
+        Keys { iter: self.keys.as_slice().iter() }
+    }
+
+    /// Gets an iterator over the values of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn values(&self) -> Values<'_, V> {
+        Values { iter: self.values.as_slice().iter() }
+    }
+
+    /// Gets a mutable iterator over the values of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        ValuesMut { iter: self.values.as_mut_slice().iter_mut() }
+    }
+
+    /// Gets an iterator over the entries of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::ArrayLinearMap;
+    ///
+    /// let mut map: ArrayLinearMap<_, _, 4> = ArrayLinearMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    ///
+    /// for (key, value) in map.iter() {
+    ///     println!("{}: {}", key, value);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            key: self.keys.as_slice().iter(),
+            value: self.values.as_slice().iter(),
+        }
+    }
+
+    /// Gets a mutable iterator over the entries of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            key: self.keys.as_slice().iter(),
+            value: self.values.as_mut_slice().iter_mut(),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) fn as_slices(&self) -> (&[K], &[V]) {
+        (self.keys.as_slice(), self.values.as_slice())
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) fn as_mut_slices(&mut self) -> (&[K], &mut [V]) {
+        (self.keys.as_slice(), self.values.as_mut_slice())
+    }
+
+    #[inline]
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized
+    {
+        find(self.keys.as_slice(), key)
+    }
+}
+
+impl<K, V, const N: usize> Default for ArrayLinearMap<K, V, N>
+    where K: PartialEq
+{
+    /// Creates an empty `ArrayLinearMap`.
+    fn default() -> Self {
+        ArrayLinearMap::new()
+    }
+}
+
+impl<K, V, const N: usize> fmt::Debug for ArrayLinearMap<K, V, N>
+    where
+        K: PartialEq + fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, const N: usize> PartialEq for ArrayLinearMap<K, V, N>
+    where
+        K: PartialEq,
+        V: PartialEq,
+{
+    fn eq(&self, other: &ArrayLinearMap<K, V, N>) -> bool {
+        self.len() == other.len() &&
+            self.iter().all(|(key, value)| other.get(key).is_some_and(|v| *value == *v))
+    }
+}
+
+impl<K, V, const N: usize> Eq for ArrayLinearMap<K, V, N>
+    where
+        K: Eq,
+        V: Eq,
+{
+}
+
+/// Collects the entries into a map.
+///
+/// # Panics
+///
+/// Panics if the iterator yields more than `N` distinct keys.
+impl<K, V, const N: usize> FromIterator<(K, V)> for ArrayLinearMap<K, V, N>
+    where K: PartialEq
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> ArrayLinearMap<K, V, N> {
+        let mut map = ArrayLinearMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+/// Inserts all entries of the iterator. Entries with a key that is already present replace
+/// the value in the map.
+///
+/// # Panics
+///
+/// Panics if a new key is inserted into a full map.
+impl<K, V, const N: usize> Extend<(K, V)> for ArrayLinearMap<K, V, N>
+    where K: PartialEq
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize> Extend<(&'a K, &'a V)> for ArrayLinearMap<K, V, N>
+    where
+        K: PartialEq + Copy + 'a,
+        V: Copy + 'a,
+{
+    fn extend<T: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+impl<K, Q, V, const N: usize> Index<&Q> for ArrayLinearMap<K, V, N>
+    where
+        K: PartialEq + Borrow<Q>,
+        Q: PartialEq + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a ArrayLinearMap<K, V, N>
+    where
+        K: PartialEq + 'a,
+        V: 'a
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a mut ArrayLinearMap<K, V, N>
+    where
+        K: PartialEq + 'a,
+        V: 'a
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+/// An owning iterator over the entries of an ArrayLinearMap.
+///
+/// This struct is created by the `into_iter` method on [`ArrayLinearMap`](struct.ArrayLinearMap.html). See its documentation for more.
+pub struct IntoIter<K, V, const N: usize> {
+    key: inline_vec::IntoIter<K, N>,
+    value: inline_vec::IntoIter<V, N>,
+}
+
+impl<K, V, const N: usize> Iterator for IntoIter<K, V, N> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        if let Some(key) = self.key.next() {
+            let value = self.value.next().unwrap();
+            Some((key, value))
+        } else {
+            None
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> IntoIterator for ArrayLinearMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, N>;
+
+    fn into_iter(self) -> IntoIter<K, V, N> {
+        IntoIter {
+            key: self.keys.into_iter(),
+            value: self.values.into_iter(),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Deserialization of [`LinearMap`](../struct.LinearMap.html) and
+//! [`LinearSet`](../struct.LinearSet.html) with a configurable handling of duplicate keys.
+//!
+//! The `Deserialize` implementation of `LinearMap` builds the map via `insert`, so the last
+//! value of a duplicate key wins. [`LinearMapSeed`](struct.LinearMapSeed.html) and the
+//! functions of this module select a different [`DuplicateKeys`](enum.DuplicateKeys.html)
+//! policy.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate linear_map;
+//! extern crate serde;
+//! extern crate serde_json;
+//!
+//! use linear_map::LinearMap;
+//! use linear_map::de::{DuplicateKeys, LinearMapSeed};
+//! use serde_json::de::Deserializer;
+//! use serde::de::DeserializeSeed;
+//!
+//! let json = r#"{"a": 1, "a": 2}"#;
+//!
+//! let map: LinearMap<String, u32> = serde_json::from_str(json).unwrap();
+//! assert_eq!(map["a"], 2);
+//!
+//! let seed = LinearMapSeed::<String, u32>::new(DuplicateKeys::FirstWins);
+//! let map = seed.deserialize(&mut Deserializer::from_str(json)).unwrap();
+//! assert_eq!(map["a"], 1);
+//!
+//! let seed = LinearMapSeed::<String, u32>::new(DuplicateKeys::Error);
+//! assert!(seed.deserialize(&mut Deserializer::from_str(json)).is_err());
+//! ```
+
+use core::cmp;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use {Entry, LinearMap, LinearSet};
+
+/// Upper bound for the capacity that is reserved based on the size hint of the input, so
+/// that malformed input cannot trigger huge allocations.
+const MAX_PREALLOCATED: usize = 4096;
+
+/// Determines what happens when the input contains the same key more than once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// The value of the last occurrence is kept, like with `LinearMap::insert`.
+    #[default]
+    LastWins,
+    /// The value of the first occurrence is kept and later ones are ignored.
+    FirstWins,
+    /// Deserialization fails.
+    Error,
+}
+
+/// Deserializes a `LinearMap` with the given [`DuplicateKeys`](enum.DuplicateKeys.html)
+/// policy.
+pub struct LinearMapSeed<K, V> {
+    duplicate_keys: DuplicateKeys,
+    marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> LinearMapSeed<K, V> {
+    /// Creates a seed that handles duplicate keys according to `duplicate_keys`.
+    pub fn new(duplicate_keys: DuplicateKeys) -> Self {
+        LinearMapSeed {
+            duplicate_keys,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Clone for LinearMapSeed<K, V> {
+    fn clone(&self) -> Self {
+        LinearMapSeed::new(self.duplicate_keys)
+    }
+}
+
+impl<'de, K, V> DeserializeSeed<'de> for LinearMapSeed<K, V>
+    where
+        K: PartialEq + Deserialize<'de>,
+        V: Deserialize<'de>,
+{
+    type Value = LinearMap<K, V>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<LinearMap<K, V>, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, K, V> Visitor<'de> for LinearMapSeed<K, V>
+    where
+        K: PartialEq + Deserialize<'de>,
+        V: Deserialize<'de>,
+{
+    type Value = LinearMap<K, V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<LinearMap<K, V>, A::Error> {
+        let capacity = cmp::min(access.size_hint().unwrap_or(0), MAX_PREALLOCATED);
+        let mut map = LinearMap::with_capacity(capacity);
+        while let Some((key, value)) = access.next_entry()? {
+            match map.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+                Entry::Occupied(mut entry) => match self.duplicate_keys {
+                    DuplicateKeys::LastWins => {
+                        entry.insert(value);
+                    }
+                    DuplicateKeys::FirstWins => {}
+                    DuplicateKeys::Error => return Err(de::Error::custom("duplicate key in map")),
+                },
+            }
+        }
+        Ok(map)
+    }
+}
+
+/// Builds the map via `insert`, so the last value of a duplicate key wins.
+impl<'de, K, V> Deserialize<'de> for LinearMap<K, V>
+    where
+        K: PartialEq + Deserialize<'de>,
+        V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<LinearMap<K, V>, D::Error> {
+        LinearMapSeed::new(DuplicateKeys::LastWins).deserialize(deserializer)
+    }
+}
+
+/// Deserializes a `LinearMap` keeping the first value of a duplicate key. Can be used with
+/// `#[serde(deserialize_with = "linear_map::de::first_wins")]`.
+pub fn first_wins<'de, D, K, V>(deserializer: D) -> Result<LinearMap<K, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        K: PartialEq + Deserialize<'de>,
+        V: Deserialize<'de>,
+{
+    LinearMapSeed::new(DuplicateKeys::FirstWins).deserialize(deserializer)
+}
+
+/// Deserializes a `LinearMap` failing on duplicate keys. Can be used with
+/// `#[serde(deserialize_with = "linear_map::de::deny_duplicates")]`.
+pub fn deny_duplicates<'de, D, K, V>(deserializer: D) -> Result<LinearMap<K, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        K: PartialEq + Deserialize<'de>,
+        V: Deserialize<'de>,
+{
+    LinearMapSeed::new(DuplicateKeys::Error).deserialize(deserializer)
+}
+
+struct LinearSetVisitor<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> Visitor<'de> for LinearSetVisitor<T>
+    where T: PartialEq + Deserialize<'de>
+{
+    type Value = LinearSet<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut access: A) -> Result<LinearSet<T>, A::Error> {
+        let capacity = cmp::min(access.size_hint().unwrap_or(0), MAX_PREALLOCATED);
+        let mut set = LinearSet::with_capacity(capacity);
+        while let Some(value) = access.next_element()? {
+            set.insert(value);
+        }
+        Ok(set)
+    }
+}
+
+/// Builds the set via `insert`, so duplicate values are ignored.
+impl<'de, T> Deserialize<'de> for LinearSet<T>
+    where T: PartialEq + Deserialize<'de>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<LinearSet<T>, D::Error> {
+        deserializer.deserialize_seq(LinearSetVisitor { marker: PhantomData })
+    }
+}
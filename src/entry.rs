@@ -0,0 +1,389 @@
+use core::mem;
+
+use LinearMap;
+
+/// A view into a single entry in a map, which may either be vacant or occupied.
+///
+/// This enum is constructed from the `entry` method on [`LinearMap`](struct.LinearMap.html).
+pub enum Entry<'a, K: 'a, V: 'a>
+    where K: PartialEq
+{
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// A vacant entry.
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// A view into an occupied entry in a `LinearMap`. It is part of the [`Entry`](enum.Entry.html) enum.
+pub struct OccupiedEntry<'a, K: 'a, V: 'a>
+    where K: PartialEq
+{
+    map: &'a mut LinearMap<K, V>,
+    index: usize,
+}
+
+/// A view into a vacant entry in a `LinearMap`. It is part of the [`Entry`](enum.Entry.html) enum.
+pub struct VacantEntry<'a, K: 'a, V: 'a>
+    where K: PartialEq
+{
+    map: &'a mut LinearMap<K, V>,
+    key: K,
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+    where K: PartialEq
+{
+    pub(crate) fn new(map: &'a mut LinearMap<K, V>, key: K) -> Self {
+        let index = map.find(&key);
+        Entry::with_index(map, key, index)
+    }
+
+    /// Creates the entry of `key` from its already known position in `map`.
+    pub(crate) fn with_index(map: &'a mut LinearMap<K, V>, key: K, index: Option<usize>) -> Self {
+        match index {
+            Some(index) => Entry::Occupied(OccupiedEntry { map, index }),
+            None => Entry::Vacant(VacantEntry { map, key }),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default if empty, and returns
+    /// a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map: LinearMap<&str, u32> = LinearMap::new();
+    /// map.entry("poneyland").or_insert(3);
+    /// assert_eq!(map.get("poneyland"), Some(&3));
+    ///
+    /// *map.entry("poneyland").or_insert(10) *= 2;
+    /// assert_eq!(map.get("poneyland"), Some(&6));
+    /// ```
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if
+    /// empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map: LinearMap<&str, String> = LinearMap::new();
+    /// let s = "hoho".to_string();
+    ///
+    /// map.entry("poneyland").or_insert_with(|| s);
+    /// assert_eq!(map.get("poneyland"), Some(&"hoho".to_string()));
+    /// ```
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if
+    /// empty, and returns a mutable reference to the value in the entry. The function is
+    /// given a reference to the key that was moved into the `entry` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map: LinearMap<&str, usize> = LinearMap::new();
+    ///
+    /// map.entry("poneyland").or_insert_with_key(|key| key.chars().count());
+    /// assert_eq!(map.get("poneyland"), Some(&9));
+    /// ```
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = default(&entry.key);
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Returns a reference to this entry's key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map: LinearMap<&str, u32> = LinearMap::new();
+    /// assert_eq!(map.entry("poneyland").key(), &"poneyland");
+    /// ```
+    pub fn key(&self) -> &K {
+        match *self {
+            Entry::Occupied(ref entry) => entry.key(),
+            Entry::Vacant(ref entry) => entry.key(),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential
+    /// inserts into the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map: LinearMap<&str, u32> = LinearMap::new();
+    ///
+    /// map.entry("poneyland")
+    ///    .and_modify(|e| { *e += 1 })
+    ///    .or_insert(42);
+    /// assert_eq!(map.get("poneyland"), Some(&42));
+    ///
+    /// map.entry("poneyland")
+    ///    .and_modify(|e| { *e += 1 })
+    ///    .or_insert(42);
+    /// assert_eq!(map.get("poneyland"), Some(&43));
+    /// ```
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+    where
+        K: PartialEq,
+        V: Default,
+{
+    /// Ensures a value is in the entry by inserting the default value if empty, and
+    /// returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map: LinearMap<&str, Option<u32>> = LinearMap::new();
+    /// map.entry("poneyland").or_default();
+    ///
+    /// assert_eq!(map.get("poneyland"), Some(&None));
+    /// ```
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+    where K: PartialEq
+{
+    /// Gets a reference to the key in the entry.
+    pub fn key(&self) -> &K {
+        &self.map.keys[self.index]
+    }
+
+    /// Gets a reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::{LinearMap, Entry};
+    ///
+    /// let mut map: LinearMap<&str, u32> = LinearMap::new();
+    /// map.entry("poneyland").or_insert(12);
+    ///
+    /// if let Entry::Occupied(o) = map.entry("poneyland") {
+    ///     assert_eq!(o.get(), &12);
+    /// }
+    /// ```
+    pub fn get(&self) -> &V {
+        &self.map.values[self.index]
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    ///
+    /// If you need a reference to the `OccupiedEntry` which may outlive the destruction
+    /// of the `Entry` value, see `into_mut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::{LinearMap, Entry};
+    ///
+    /// let mut map: LinearMap<&str, u32> = LinearMap::new();
+    /// map.entry("poneyland").or_insert(12);
+    ///
+    /// if let Entry::Occupied(mut o) = map.entry("poneyland") {
+    ///     *o.get_mut() += 10;
+    ///     assert_eq!(*o.get(), 22);
+    /// }
+    /// assert_eq!(map.get("poneyland"), Some(&22));
+    /// ```
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.values[self.index]
+    }
+
+    /// Converts the entry into a mutable reference to the value in the entry with a
+    /// lifetime bound to the map itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::{LinearMap, Entry};
+    ///
+    /// let mut map: LinearMap<&str, u32> = LinearMap::new();
+    /// map.entry("poneyland").or_insert(12);
+    ///
+    /// if let Entry::Occupied(o) = map.entry("poneyland") {
+    ///     *o.into_mut() += 10;
+    /// }
+    /// assert_eq!(map.get("poneyland"), Some(&22));
+    /// ```
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.values[self.index]
+    }
+
+    /// Sets the value of the entry and returns the entry's old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::{LinearMap, Entry};
+    ///
+    /// let mut map: LinearMap<&str, u32> = LinearMap::new();
+    /// map.entry("poneyland").or_insert(12);
+    ///
+    /// if let Entry::Occupied(mut o) = map.entry("poneyland") {
+    ///     assert_eq!(o.insert(15), 12);
+    /// }
+    /// assert_eq!(map.get("poneyland"), Some(&15));
+    /// ```
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Takes the value out of the entry and returns it.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::{LinearMap, Entry};
+    ///
+    /// let mut map: LinearMap<&str, u32> = LinearMap::new();
+    /// map.entry("poneyland").or_insert(12);
+    ///
+    /// if let Entry::Occupied(o) = map.entry("poneyland") {
+    ///     assert_eq!(o.remove(), 12);
+    /// }
+    /// assert!(!map.contains_key("poneyland"));
+    /// ```
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Takes ownership of the key and value from the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::{LinearMap, Entry};
+    ///
+    /// let mut map: LinearMap<&str, u32> = LinearMap::new();
+    /// map.entry("poneyland").or_insert(12);
+    ///
+    /// if let Entry::Occupied(o) = map.entry("poneyland") {
+    ///     assert_eq!(o.remove_entry(), ("poneyland", 12));
+    /// }
+    /// assert!(!map.contains_key("poneyland"));
+    /// ```
+    pub fn remove_entry(self) -> (K, V) {
+        let key = self.map.keys.swap_remove(self.index);
+        let value = self.map.values.swap_remove(self.index);
+        (key, value)
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V>
+    where K: PartialEq
+{
+    /// Gets a reference to the key that would be used when inserting a value through
+    /// the `VacantEntry`.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes ownership of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::{LinearMap, Entry};
+    ///
+    /// let mut map: LinearMap<String, u32> = LinearMap::new();
+    ///
+    /// if let Entry::Vacant(v) = map.entry("poneyland".to_string()) {
+    ///     assert_eq!(v.into_key(), "poneyland");
+    /// }
+    /// ```
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Sets the value of the entry with the `VacantEntry`'s key, and returns a mutable
+    /// reference to it.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1) amortized
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::{LinearMap, Entry};
+    ///
+    /// let mut map: LinearMap<&str, u32> = LinearMap::new();
+    ///
+    /// if let Entry::Vacant(o) = map.entry("poneyland") {
+    ///     o.insert(37);
+    /// }
+    /// assert_eq!(map.get("poneyland"), Some(&37));
+    /// ```
+    pub fn insert(self, value: V) -> &'a mut V {
+        let map = self.map;
+        map.keys.push(self.key);
+        map.values.push(value);
+        map.values.last_mut().unwrap()
+    }
+}
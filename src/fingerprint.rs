@@ -0,0 +1,619 @@
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{BuildHasher, Hash, Hasher};
+use core::iter::FromIterator;
+use core::mem;
+use core::ops::Index;
+
+use alloc::vec::Vec;
+
+use {simd, IntoIter, Iter, IterMut, Keys, LinearMap, Values, ValuesMut};
+
+/// FingerprintLinearMap is a [`LinearMap`](struct.LinearMap.html) that stores a one byte
+/// hash fingerprint for every key in an array next to the keys and values. A lookup first
+/// compares the fingerprints, which is done 16 or 32 at a time with SIMD instructions where
+/// available, and only calls `PartialEq` for the keys whose fingerprint matches.
+///
+/// This pays off for keys that are expensive to compare, like `String`s with common
+/// prefixes or large structs. For cheap keys the hashing costs more than it saves. Keys and
+/// values are stored like in LinearMap, so iteration is just as fast.
+///
+/// The hasher `S` defaults to [`FxBuildHasher`](fingerprint/struct.FxBuildHasher.html),
+/// which is fast and does not require `std`. Like with `HashMap`, a key must hash the same
+/// way as the borrowed form that is used to look it up.
+///
+/// # Examples
+///
+/// ```
+/// extern crate linear_map;
+/// use linear_map::FingerprintLinearMap;
+///
+/// let mut map = FingerprintLinearMap::new();
+/// map.insert("https://example.com/a".to_string(), 1);
+/// map.insert("https://example.com/b".to_string(), 2);
+///
+/// assert_eq!(map.get("https://example.com/b"), Some(&2));
+/// assert_eq!(map.remove("https://example.com/a"), Some(1));
+/// assert_eq!(map.len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct FingerprintLinearMap<K, V, S = FxBuildHasher>
+    where K: PartialEq
+{
+    map: LinearMap<K, V>,
+    fingerprints: Vec<u8>,
+    hash_builder: S,
+}
+
+impl<K, V> FingerprintLinearMap<K, V>
+    where K: PartialEq + Hash
+{
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        FingerprintLinearMap::with_hasher(FxBuildHasher)
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        FingerprintLinearMap::with_capacity_and_hasher(capacity, FxBuildHasher)
+    }
+}
+
+impl<K, V, S> FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + Hash,
+        S: BuildHasher,
+{
+    /// Creates an empty map which uses `hash_builder` to compute the fingerprints.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        FingerprintLinearMap {
+            map: LinearMap::new(),
+            fingerprints: Vec::new(),
+            hash_builder,
+        }
+    }
+
+    /// Creates an empty map with the given initial capacity which uses `hash_builder` to
+    /// compute the fingerprints.
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        FingerprintLinearMap {
+            map: LinearMap::with_capacity(capacity),
+            fingerprints: Vec::with_capacity(capacity),
+            hash_builder,
+        }
+    }
+
+    /// Creates a map from the entries of `map`, computing their fingerprints with
+    /// `hash_builder`.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn from_linear_map_with_hasher(map: LinearMap<K, V>, hash_builder: S) -> Self {
+        let fingerprints = map.keys().map(|key| fingerprint(&hash_builder, key)).collect();
+        FingerprintLinearMap { map, fingerprints, hash_builder }
+    }
+
+    /// Returns a reference to the map's hasher.
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Clears the map, removing all elements. Keeps the allocated memory for reuse.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.fingerprints.clear();
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::FingerprintLinearMap;
+    ///
+    /// let mut map = FingerprintLinearMap::new();
+    /// map.insert(vec![1, 2, 3], "a");
+    /// assert_eq!(map.get(&[1, 2, 3][..]), Some(&"a"));
+    /// assert_eq!(map.get(&[1, 2][..]), None);
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + Hash + ?Sized,
+    {
+        self.find(key).map(|i| &self.map.values[i])
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + Hash + ?Sized,
+    {
+        match self.find(key) {
+            Some(i) => Some(&mut self.map.values[i]),
+            None => None,
+        }
+    }
+
+    /// Returns the key-value pair corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + Hash + ?Sized,
+    {
+        self.find(key).map(|i| (&self.map.keys[i], &self.map.values[i]))
+    }
+
+    /// Returns true if the map contains a value for the specified key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + Hash + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, `None` is returned. If the map did have
+    /// this key present, the value is updated, and the old value is returned.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::FingerprintLinearMap;
+    ///
+    /// let mut map = FingerprintLinearMap::new();
+    /// assert_eq!(map.insert("a".to_string(), 1), None);
+    /// assert_eq!(map.insert("a".to_string(), 2), Some(1));
+    /// assert_eq!(map["a"], 2);
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let fingerprint = fingerprint(&self.hash_builder, &key);
+        match self.find_with_fingerprint(&key, fingerprint) {
+            Some(i) => Some(mem::replace(&mut self.map.values[i], value)),
+            None => {
+                self.map.keys.push(key);
+                self.map.values.push(value);
+                self.fingerprints.push(fingerprint);
+                None
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously
+    /// in the map. The last element of the map takes the place of the removed one, like
+    /// [`LinearMap::swap_remove`](struct.LinearMap.html#method.swap_remove).
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + Hash + ?Sized,
+    {
+        self.swap_remove(key)
+    }
+
+    /// Removes a key from the map by swapping the last element into its place, returning the
+    /// value at the key if the key was previously in the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + Hash + ?Sized,
+    {
+        self.swap_remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes a key from the map by swapping the last element into its place, returning the
+    /// stored key and value if the key was previously in the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn swap_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + Hash + ?Sized,
+    {
+        self.find(key).map(|i| {
+            self.fingerprints.swap_remove(i);
+            (self.map.keys.swap_remove(i), self.map.values.swap_remove(i))
+        })
+    }
+
+    /// Removes a key from the map while preserving the order of the remaining elements,
+    /// returning the value at the key if the key was previously in the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + Hash + ?Sized,
+    {
+        self.shift_remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes a key from the map while preserving the order of the remaining elements,
+    /// returning the stored key and value if the key was previously in the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn shift_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + Hash + ?Sized,
+    {
+        self.find(key).map(|i| {
+            self.fingerprints.remove(i);
+            (self.map.keys.remove(i), self.map.values.remove(i))
+        })
+    }
+
+    /// Returns an iterator over the keys in [storage order](struct.LinearMap.html#ordering).
+    pub fn keys(&self) -> Keys<'_, K> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator over the values in [storage order](struct.LinearMap.html#ordering).
+    pub fn values(&self) -> Values<'_, V> {
+        self.map.values()
+    }
+
+    /// Returns an iterator over mutable references to the values in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        self.map.values_mut()
+    }
+
+    /// Returns an iterator over the key-value pairs in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// Returns an iterator over the key-value pairs with mutable references to the values in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Returns the underlying `LinearMap`.
+    pub fn as_linear_map(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+
+    /// Converts the map into a `LinearMap` without copying the entries.
+    pub fn into_linear_map(self) -> LinearMap<K, V> {
+        self.map
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + Hash + ?Sized,
+    {
+        self.find_with_fingerprint(key, fingerprint(&self.hash_builder, key))
+    }
+
+    fn find_with_fingerprint<Q>(&self, key: &Q, fingerprint: u8) -> Option<usize>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        find(&self.fingerprints, &self.map.keys, key, fingerprint)
+    }
+}
+
+/// Returns the position of `key` in `keys`, comparing only the keys whose entry in
+/// `fingerprints` equals `fingerprint`.
+pub(crate) fn find<K, Q>(fingerprints: &[u8], keys: &[K], key: &Q, fingerprint: u8) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+{
+    let mut start = 0;
+    while let Some(i) = simd::find(&fingerprints[start..], &[fingerprint]) {
+        let index = start + i;
+        if key.eq(keys[index].borrow()) {
+            return Some(index);
+        }
+        start = index + 1;
+    }
+    None
+}
+
+/// Returns the fingerprint of `key`, which is the top byte of its hash. The top bits of
+/// multiplicative hashes like [`FxHasher`](fingerprint/struct.FxHasher.html) are the best
+/// mixed ones.
+#[inline]
+pub(crate) fn fingerprint<Q, S>(hash_builder: &S, key: &Q) -> u8
+    where
+        Q: Hash + ?Sized,
+        S: BuildHasher,
+{
+    (hash_builder.hash_one(key) >> 56) as u8
+}
+
+/// A fast non-cryptographic hasher, the same algorithm that rustc uses for its hash maps.
+///
+/// It is the default hasher of [`FingerprintLinearMap`](../struct.FingerprintLinearMap.html).
+/// Since only a byte of the hash is kept and the keys are still compared with `PartialEq`,
+/// HashDoS resistance is not a concern there.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    #[inline]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            self.add_to_hash(u64::from_le_bytes(word));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add_to_hash(u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Builds [`FxHasher`](struct.FxHasher.html)s.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FxBuildHasher;
+
+impl BuildHasher for FxBuildHasher {
+    type Hasher = FxHasher;
+
+    fn build_hasher(&self) -> FxHasher {
+        FxHasher::default()
+    }
+}
+
+impl<K, V, S> Default for FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + Hash,
+        S: BuildHasher + Default,
+{
+    /// Creates an empty `FingerprintLinearMap`.
+    fn default() -> Self {
+        FingerprintLinearMap::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> fmt::Debug for FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K, V, S> PartialEq for FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + Hash,
+        V: PartialEq,
+        S: BuildHasher,
+{
+    fn eq(&self, other: &FingerprintLinearMap<K, V, S>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K, V, S> Eq for FingerprintLinearMap<K, V, S>
+    where
+        K: Eq + Hash,
+        V: Eq,
+        S: BuildHasher,
+{
+}
+
+impl<K, V, S> From<LinearMap<K, V>> for FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + Hash,
+        S: BuildHasher + Default,
+{
+    fn from(map: LinearMap<K, V>) -> Self {
+        FingerprintLinearMap::from_linear_map_with_hasher(map, S::default())
+    }
+}
+
+impl<K, V, S> From<FingerprintLinearMap<K, V, S>> for LinearMap<K, V>
+    where K: PartialEq
+{
+    fn from(map: FingerprintLinearMap<K, V, S>) -> Self {
+        map.map
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + Hash,
+        S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = FingerprintLinearMap::default();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + Hash,
+        S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let additional = iter.size_hint().0;
+        self.map.keys.reserve(additional);
+        self.map.values.reserve(additional);
+        self.fingerprints.reserve(additional);
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V, S> Extend<(&'a K, &'a V)> for FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + Hash + Copy + 'a,
+        V: Copy + 'a,
+        S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+impl<K, V, S, Q> Index<&Q> for FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + Hash + Borrow<Q>,
+        Q: PartialEq + Hash + ?Sized,
+        S: BuildHasher,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + Hash,
+        S: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut FingerprintLinearMap<K, V, S>
+    where
+        K: PartialEq + Hash,
+        S: BuildHasher,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, S> IntoIterator for FingerprintLinearMap<K, V, S>
+    where K: PartialEq
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
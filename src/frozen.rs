@@ -0,0 +1,336 @@
+//! Immutable maps whose storage is arranged for lookups, created by
+//! [`LinearMap::freeze`](../struct.LinearMap.html#method.freeze) and its variants.
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{BuildHasher, Hash};
+use core::ops::Index;
+
+use alloc::boxed::Box;
+
+use fingerprint::{self, FxBuildHasher};
+use {find, Iter, Keys, LinearMap, SortedLinearMap, Values};
+
+/// Up to this number of entries, a linear scan is faster than the binary search of
+/// [`Sorted`](struct.Sorted.html) or computing the hash for [`Hashed`](struct.Hashed.html).
+const SCAN_LEN: usize = 8;
+
+/// An index that finds keys of type `Q` in the keys of a
+/// [`FrozenLinearMap`](../struct.FrozenLinearMap.html).
+///
+/// The index is built when the map is frozen and may rely on the order in which the keys
+/// were arranged at that point.
+pub trait Lookup<K, Q: ?Sized> {
+    /// Returns the position of `key` in `keys`.
+    fn find(&self, keys: &[K], key: &Q) -> Option<usize>;
+}
+
+/// Searches the keys linearly in the order in which they were inserted.
+///
+/// Created by [`LinearMap::freeze_scan`](../struct.LinearMap.html#method.freeze_scan).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Scan;
+
+impl<K, Q> Lookup<K, Q> for Scan
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+{
+    #[inline]
+    fn find(&self, keys: &[K], key: &Q) -> Option<usize> {
+        find(keys, key)
+    }
+}
+
+/// Searches the keys, which are sorted, with binary search, or linearly if there are only a
+/// few of them.
+///
+/// Created by [`LinearMap::freeze`](../struct.LinearMap.html#method.freeze).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sorted;
+
+impl<K, Q> Lookup<K, Q> for Sorted
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+{
+    #[inline]
+    fn find(&self, keys: &[K], key: &Q) -> Option<usize> {
+        if keys.len() <= SCAN_LEN {
+            find(keys, key)
+        } else {
+            keys.binary_search_by(|k| k.borrow().cmp(key)).ok()
+        }
+    }
+}
+
+/// Compares a one byte hash fingerprint of every key before calling `PartialEq`, like
+/// [`FingerprintLinearMap`](../struct.FingerprintLinearMap.html).
+///
+/// Created by [`LinearMap::freeze_hashed`](../struct.LinearMap.html#method.freeze_hashed).
+#[derive(Clone, Debug)]
+pub struct Hashed<S = FxBuildHasher> {
+    fingerprints: Box<[u8]>,
+    hash_builder: S,
+}
+
+impl<K, Q, S> Lookup<K, Q> for Hashed<S>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + Hash + ?Sized,
+        S: BuildHasher,
+{
+    #[inline]
+    fn find(&self, keys: &[K], key: &Q) -> Option<usize> {
+        if self.fingerprints.is_empty() {
+            find(keys, key)
+        } else {
+            let fingerprint = fingerprint::fingerprint(&self.hash_builder, key);
+            fingerprint::find(&self.fingerprints, keys, key, fingerprint)
+        }
+    }
+}
+
+/// FrozenLinearMap is an immutable map created from a [`LinearMap`](struct.LinearMap.html)
+/// when it is not going to be modified anymore, like a configuration that is built once at
+/// startup and then only read.
+///
+/// The keys and values are stored in boxed slices without spare capacity, and the index `I`
+/// determines how keys are found:
+///
+/// * [`Sorted`](frozen/struct.Sorted.html), created by
+///   [`freeze`](struct.LinearMap.html#method.freeze), sorts the keys and uses binary
+///   search. This is the fastest layout for `Ord` keys.
+/// * [`Hashed`](frozen/struct.Hashed.html), created by
+///   [`freeze_hashed`](struct.LinearMap.html#method.freeze_hashed), compares hash
+///   fingerprints before the keys, for keys that implement `Hash` but not `Ord`.
+/// * [`Scan`](frozen/struct.Scan.html), created by
+///   [`freeze_scan`](struct.LinearMap.html#method.freeze_scan), searches linearly like
+///   `LinearMap` does, for keys that implement neither.
+///
+/// `Sorted` and `Hashed` fall back to a linear scan for maps with only a few entries.
+/// [`thaw`](#method.thaw) turns the map back into a `LinearMap`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate linear_map;
+/// use linear_map::LinearMap;
+///
+/// let mut config = LinearMap::new();
+/// config.insert("threads", 4);
+/// config.insert("retries", 3);
+///
+/// let config = config.freeze();
+/// assert_eq!(config.get("retries"), Some(&3));
+/// assert!(config.keys().eq(&["retries", "threads"]));
+///
+/// let mut config = config.thaw();
+/// config.insert("timeout", 30);
+/// ```
+#[derive(Clone)]
+pub struct FrozenLinearMap<K, V, I = Scan> {
+    keys: Box<[K]>,
+    values: Box<[V]>,
+    index: I,
+}
+
+impl<K, V> FrozenLinearMap<K, V, Scan>
+    where K: PartialEq
+{
+    pub(crate) fn scan(map: LinearMap<K, V>) -> Self {
+        FrozenLinearMap {
+            keys: map.keys.into_boxed_slice(),
+            values: map.values.into_boxed_slice(),
+            index: Scan,
+        }
+    }
+}
+
+impl<K, V> FrozenLinearMap<K, V, Sorted>
+    where K: Ord
+{
+    pub(crate) fn sorted(map: LinearMap<K, V>) -> Self {
+        FrozenLinearMap::scan(SortedLinearMap::from(map).into_linear_map()).with_index(Sorted)
+    }
+}
+
+impl<K, V, S> FrozenLinearMap<K, V, Hashed<S>>
+    where
+        K: PartialEq + Hash,
+        S: BuildHasher,
+{
+    pub(crate) fn hashed(map: LinearMap<K, V>, hash_builder: S) -> Self {
+        let fingerprints = if map.len() <= SCAN_LEN {
+            Box::default()
+        } else {
+            map.keys.iter().map(|key| fingerprint::fingerprint(&hash_builder, key)).collect()
+        };
+        FrozenLinearMap::scan(map).with_index(Hashed { fingerprints, hash_builder })
+    }
+}
+
+impl<K, V, I> FrozenLinearMap<K, V, I> {
+    fn with_index<J>(self, index: J) -> FrozenLinearMap<K, V, J> {
+        FrozenLinearMap { keys: self.keys, values: self.values, index }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) for `Scan` and `Hashed`, O(log n) for `Sorted`, where n is the number of
+    /// elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let map: LinearMap<_, _> = (0..100).map(|i| (i.to_string(), i)).collect();
+    /// let map = map.freeze_hashed();
+    /// assert_eq!(map.get("42"), Some(&42));
+    /// assert_eq!(map.get("100"), None);
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            I: Lookup<K, Q>,
+            Q: ?Sized,
+    {
+        self.index.find(&self.keys, key).map(|i| &self.values[i])
+    }
+
+    /// Returns the key-value pair corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) for `Scan` and `Hashed`, O(log n) for `Sorted`, where n is the number of
+    /// elements in the map.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+        where
+            I: Lookup<K, Q>,
+            Q: ?Sized,
+    {
+        self.index.find(&self.keys, key).map(|i| (&self.keys[i], &self.values[i]))
+    }
+
+    /// Returns true if the map contains a value for the specified key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) for `Scan` and `Hashed`, O(log n) for `Sorted`, where n is the number of
+    /// elements in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            I: Lookup<K, Q>,
+            Q: ?Sized,
+    {
+        self.index.find(&self.keys, key).is_some()
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns an iterator over the keys. They are in insertion order for `Scan` and
+    /// `Hashed` and in ascending order for `Sorted`.
+    pub fn keys(&self) -> Keys<'_, K> {
+        Keys { iter: self.keys.iter() }
+    }
+
+    /// Returns an iterator over the values in the order of their keys.
+    pub fn values(&self) -> Values<'_, V> {
+        Values { iter: self.values.iter() }
+    }
+
+    /// Returns an iterator over the key-value pairs. They are in insertion order for `Scan`
+    /// and `Hashed` and in ascending order of the keys for `Sorted`.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            key: self.keys.iter(),
+            value: self.values.iter(),
+        }
+    }
+
+    /// Returns the index that is used to find keys.
+    pub fn lookup(&self) -> &I {
+        &self.index
+    }
+
+    /// Converts the map back into a `LinearMap` with the same order of the entries. The
+    /// index is dropped.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    pub fn thaw(self) -> LinearMap<K, V>
+        where K: PartialEq
+    {
+        LinearMap {
+            keys: self.keys.into_vec(),
+            values: self.values.into_vec(),
+        }
+    }
+}
+
+impl<K, V, I> fmt::Debug for FrozenLinearMap<K, V, I>
+    where
+        K: fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// The order of the entries and the indices are ignored, so a `Sorted` map can be equal to
+/// a `Scan` map.
+impl<K, V, I, J> PartialEq<FrozenLinearMap<K, V, J>> for FrozenLinearMap<K, V, I>
+    where
+        V: PartialEq,
+        J: Lookup<K, K>,
+{
+    fn eq(&self, other: &FrozenLinearMap<K, V, J>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K, V, I> Eq for FrozenLinearMap<K, V, I>
+    where
+        V: Eq,
+        I: Lookup<K, K>,
+{
+}
+
+impl<K, V, I, Q> Index<&Q> for FrozenLinearMap<K, V, I>
+    where
+        I: Lookup<K, Q>,
+        Q: ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<'a, K, V, I> IntoIterator for &'a FrozenLinearMap<K, V, I> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
@@ -0,0 +1,167 @@
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ptr;
+use core::slice;
+
+/// A vector with a fixed capacity of `N` elements which are stored inline.
+///
+/// This is the storage of [`ArrayLinearMap`](../struct.ArrayLinearMap.html). The elements
+/// `data[..len]` are initialized, the remaining ones are not.
+pub(crate) struct InlineVec<T, const N: usize> {
+    len: usize,
+    data: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> InlineVec<T, N> {
+    pub const fn new() -> Self {
+        InlineVec {
+            len: 0,
+            data: [const { MaybeUninit::uninit() }; N],
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.data.as_ptr() as *const T, self.len) }
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut T, self.len) }
+    }
+
+    /// Appends `value`, or returns it if the vector is full.
+    #[inline]
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.data[self.len] = MaybeUninit::new(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe { self.data[self.len].assume_init_read() })
+    }
+
+    /// Removes the element at `index` and replaces it with the last element.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let last = self.len - 1;
+        self.as_mut_slice().swap(index, last);
+        self.pop().unwrap()
+    }
+
+    /// Removes the element at `index` and shifts all elements after it to the left.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len, "index out of bounds");
+        unsafe {
+            let value = self.data[index].assume_init_read();
+            let ptr = self.data.as_mut_ptr().add(index);
+            ptr::copy(ptr.add(1), ptr, self.len - index - 1);
+            self.len -= 1;
+            value
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let tail = &mut self.as_mut_slice()[len..] as *mut [T];
+        // The length is updated first so that a panicking `drop` cannot lead to a
+        // double drop.
+        self.len = len;
+        unsafe { ptr::drop_in_place(tail) };
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for InlineVec<T, N> {
+    fn clone(&self) -> Self {
+        let mut clone = InlineVec::new();
+        for value in self.as_slice() {
+            // Cannot fail, the clone has the same capacity as `self`.
+            let _ = clone.push(value.clone());
+        }
+        clone
+    }
+}
+
+impl<T, const N: usize> Drop for InlineVec<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> IntoIterator for InlineVec<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> IntoIter<T, N> {
+        let vec = ManuallyDrop::new(self);
+        IntoIter {
+            start: 0,
+            end: vec.len,
+            data: unsafe { ptr::read(&vec.data) },
+        }
+    }
+}
+
+/// An owning iterator over the elements of an `InlineVec`. The elements `data[start..end]`
+/// have not been yielded yet.
+pub(crate) struct IntoIter<T, const N: usize> {
+    start: usize,
+    end: usize,
+    data: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        Some(unsafe { self.data[self.start - 1].assume_init_read() })
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(unsafe { self.data[self.end].assume_init_read() })
+    }
+}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        let remaining = &mut self.data[self.start..self.end] as *mut [MaybeUninit<T>] as *mut [T];
+        self.start = self.end;
+        unsafe { ptr::drop_in_place(remaining) };
+    }
+}
@@ -0,0 +1,432 @@
+use core::fmt;
+use core::iter::FromIterator;
+use core::mem;
+use core::ops::Index;
+use core::slice;
+
+use {simd, Entry, IntoIter, Iter, IterMut, Keys, LinearMap, Values, ValuesMut};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Key types that [`IntLinearMap`](struct.IntLinearMap.html) can search with SIMD
+/// instructions.
+///
+/// The trait is implemented for the primitive integers up to 64 bits and `char`. Two values
+/// of these types are equal exactly when their bytes are equal, which allows comparing many
+/// keys at once. The trait is sealed and cannot be implemented outside of this crate.
+pub trait IntKey: Copy + PartialEq + private::Sealed {}
+
+macro_rules! impl_int_key {
+    ($($ty:ty),*) => {
+        $(
+            impl private::Sealed for $ty {}
+            impl IntKey for $ty {}
+        )*
+    };
+}
+
+impl_int_key!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, char);
+
+/// IntLinearMap is a [`LinearMap`](struct.LinearMap.html) for primitive integer and `char`
+/// keys that compares 16 or 32 keys per step with SIMD instructions instead of comparing
+/// them one by one.
+///
+/// On x86_64 the search uses SSE2, or AVX2 when the CPU supports it. On other targets it
+/// falls back to a scalar loop. The entries are stored like in LinearMap, so the map can be
+/// converted from and to a LinearMap without copying.
+///
+/// # Examples
+///
+/// ```
+/// extern crate linear_map;
+/// use linear_map::IntLinearMap;
+///
+/// let mut map = IntLinearMap::new();
+/// for i in 0..100u16 {
+///     map.insert(i, i * 2);
+/// }
+/// assert_eq!(map.get(&42), Some(&84));
+/// assert_eq!(map.remove(&42), Some(84));
+/// assert!(!map.contains_key(&42));
+/// ```
+#[derive(Clone)]
+pub struct IntLinearMap<K, V>
+    where K: IntKey
+{
+    map: LinearMap<K, V>,
+}
+
+impl<K, V> IntLinearMap<K, V>
+    where K: IntKey
+{
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        IntLinearMap { map: LinearMap::new() }
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        IntLinearMap { map: LinearMap::with_capacity(capacity) }
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Clears the map, removing all elements. Keeps the allocated memory for reuse.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::IntLinearMap;
+    ///
+    /// let mut map = IntLinearMap::new();
+    /// map.insert('a', 1);
+    /// assert_eq!(map.get(&'a'), Some(&1));
+    /// assert_eq!(map.get(&'b'), None);
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|i| &self.map.values[i])
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self.find(key) {
+            Some(i) => Some(&mut self.map.values[i]),
+            None => None,
+        }
+    }
+
+    /// Returns the key-value pair corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        self.find(key).map(|i| (&self.map.keys[i], &self.map.values[i]))
+    }
+
+    /// Returns true if the map contains a value for the specified key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, `None` is returned. If the map did have
+    /// this key present, the value is updated, and the old value is returned.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::IntLinearMap;
+    ///
+    /// let mut map = IntLinearMap::new();
+    /// assert_eq!(map.insert(37u64, "a"), None);
+    /// assert_eq!(map.insert(37, "b"), Some("a"));
+    /// assert_eq!(map[&37], "b");
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.find(&key) {
+            Some(i) => Some(mem::replace(&mut self.map.values[i], value)),
+            None => {
+                self.map.keys.push(key);
+                self.map.values.push(value);
+                None
+            }
+        }
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::IntLinearMap;
+    ///
+    /// let mut counts = IntLinearMap::new();
+    /// for &byte in b"abracadabra" {
+    ///     *counts.entry(byte).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(counts[&b'a'], 5);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let index = self.find(&key);
+        Entry::with_index(&mut self.map, key, index)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously
+    /// in the map. The last element of the map takes the place of the removed one, like
+    /// [`LinearMap::swap_remove`](struct.LinearMap.html#method.swap_remove).
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.swap_remove(key)
+    }
+
+    /// Removes a key from the map by swapping the last element into its place, returning the
+    /// value at the key if the key was previously in the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn swap_remove(&mut self, key: &K) -> Option<V> {
+        self.find(key).map(|i| {
+            self.map.keys.swap_remove(i);
+            self.map.values.swap_remove(i)
+        })
+    }
+
+    /// Removes a key from the map while preserving the order of the remaining elements,
+    /// returning the value at the key if the key was previously in the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn shift_remove(&mut self, key: &K) -> Option<V> {
+        self.find(key).map(|i| {
+            self.map.keys.remove(i);
+            self.map.values.remove(i)
+        })
+    }
+
+    /// Returns an iterator over the keys in [storage order](struct.LinearMap.html#ordering).
+    pub fn keys(&self) -> Keys<'_, K> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator over the values in [storage order](struct.LinearMap.html#ordering).
+    pub fn values(&self) -> Values<'_, V> {
+        self.map.values()
+    }
+
+    /// Returns an iterator over mutable references to the values in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        self.map.values_mut()
+    }
+
+    /// Returns an iterator over the key-value pairs in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// Returns an iterator over the key-value pairs with mutable references to the values in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Returns the underlying `LinearMap`.
+    pub fn as_linear_map(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+
+    /// Converts the map into a `LinearMap` without copying the entries.
+    pub fn into_linear_map(self) -> LinearMap<K, V> {
+        self.map
+    }
+
+    fn find(&self, key: &K) -> Option<usize> {
+        find(&self.map.keys, key)
+    }
+}
+
+/// Returns the position of `key` in `keys` using a vectorised search.
+#[inline]
+pub(crate) fn find<K: IntKey>(keys: &[K], key: &K) -> Option<usize> {
+    // SAFETY: `IntKey` is only implemented for integers and `char`, which have no padding, so
+    // all of their bytes are initialized. Equal values have equal bytes.
+    let haystack = unsafe { slice::from_raw_parts(keys.as_ptr() as *const u8, mem::size_of_val(keys)) };
+    let key = key as *const K as *const u8;
+    // SAFETY: The needles cover exactly the bytes of `key`.
+    unsafe {
+        match mem::size_of::<K>() {
+            1 => simd::find::<1>(haystack, &*(key as *const [u8; 1])),
+            2 => simd::find::<2>(haystack, &*(key as *const [u8; 2])),
+            4 => simd::find::<4>(haystack, &*(key as *const [u8; 4])),
+            _ => simd::find::<8>(haystack, &*(key as *const [u8; 8])),
+        }
+    }
+}
+
+impl<K, V> Default for IntLinearMap<K, V>
+    where K: IntKey
+{
+    /// Creates an empty `IntLinearMap`.
+    fn default() -> Self {
+        IntLinearMap::new()
+    }
+}
+
+impl<K, V> fmt::Debug for IntLinearMap<K, V>
+    where
+        K: IntKey + fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K, V> PartialEq for IntLinearMap<K, V>
+    where
+        K: IntKey,
+        V: PartialEq,
+{
+    fn eq(&self, other: &IntLinearMap<K, V>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K, V> Eq for IntLinearMap<K, V>
+    where
+        K: IntKey,
+        V: Eq,
+{
+}
+
+impl<K, V> From<LinearMap<K, V>> for IntLinearMap<K, V>
+    where K: IntKey
+{
+    fn from(map: LinearMap<K, V>) -> Self {
+        IntLinearMap { map }
+    }
+}
+
+impl<K, V> From<IntLinearMap<K, V>> for LinearMap<K, V>
+    where K: IntKey
+{
+    fn from(map: IntLinearMap<K, V>) -> Self {
+        map.map
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for IntLinearMap<K, V>
+    where K: IntKey
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = IntLinearMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for IntLinearMap<K, V>
+    where K: IntKey
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.map.keys.reserve(iter.size_hint().0);
+        self.map.values.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> Extend<(&'a K, &'a V)> for IntLinearMap<K, V>
+    where
+        K: IntKey + 'a,
+        V: Copy + 'a,
+{
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+impl<K, V> Index<&K> for IntLinearMap<K, V>
+    where K: IntKey
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &K) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a IntLinearMap<K, V>
+    where K: IntKey
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut IntLinearMap<K, V>
+    where K: IntKey
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> IntoIterator for IntLinearMap<K, V>
+    where K: IntKey
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
@@ -18,13 +18,132 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use std::borrow::Borrow;
-use std::iter::Iterator;
-use std::mem;
-use std::vec;
-use std::slice;
+//! Maps and sets that store their entries in arrays and search them linearly.
+//!
+//! The crate is `no_std`. The `std` feature is enabled by default and implies the `alloc`
+//! feature, which provides the heap allocated collections like [`LinearMap`]. Without
+//! `alloc`, only the inline [`ArrayLinearMap`] is available. [`AdaptiveMap`] builds on
+//! `HashMap` and therefore requires `std`. [`IntLinearMap`] searches integer and `char` keys
+//! with SIMD instructions where available, and [`FingerprintLinearMap`] compares one byte
+//! hashes before calling `PartialEq` on expensive keys. [`SelfOrganizingLinearMap`] moves
+//! frequently accessed entries to the front, and [`SortedLinearMap`] keeps `Ord` keys sorted
+//! for binary search and range queries. Maps that are only read after they are built can be
+//! frozen into a [`FrozenLinearMap`].
+//!
+//! The [`linear_map!`] macro builds a `LinearMap` from `key => value` pairs, and
+//! [`static_linear_map!`] builds a [`StaticLinearMap`] over a `&'static` slice that can be
+//! stored in a `static` without allocating. Keys and values that already live in slices can
+//! be searched in place through a [`LinearMapRef`] or [`LinearMapMut`] view.
+//!
+//! The optional `serde` feature implements `Serialize` and `Deserialize` for [`LinearMap`]
+//! and [`LinearSet`]. The [`de`] module controls how duplicate keys in the input are handled.
+//!
+//! [`LinearMap`]: struct.LinearMap.html
+//! [`ArrayLinearMap`]: struct.ArrayLinearMap.html
+//! [`AdaptiveMap`]: struct.AdaptiveMap.html
+//! [`LinearSet`]: struct.LinearSet.html
+//! [`IntLinearMap`]: struct.IntLinearMap.html
+//! [`FingerprintLinearMap`]: struct.FingerprintLinearMap.html
+//! [`SelfOrganizingLinearMap`]: struct.SelfOrganizingLinearMap.html
+//! [`SortedLinearMap`]: struct.SortedLinearMap.html
+//! [`FrozenLinearMap`]: struct.FrozenLinearMap.html
+//! [`StaticLinearMap`]: struct.StaticLinearMap.html
+//! [`LinearMapRef`]: struct.LinearMapRef.html
+//! [`LinearMapMut`]: struct.LinearMapMut.html
+//! [`linear_map!`]: macro.linear_map.html
+//! [`static_linear_map!`]: macro.static_linear_map.html
+//! [`de`]: de/index.html
 
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+#[macro_use]
+extern crate std;
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(feature = "alloc")]
+use alloc::collections::TryReserveError;
+#[cfg(feature = "alloc")]
+use alloc::vec::{self, Vec};
+use core::borrow::Borrow;
+#[cfg(feature = "alloc")]
+use core::cmp;
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "alloc")]
+use core::iter::FromIterator;
+use core::iter::{FusedIterator, Iterator};
+#[cfg(feature = "alloc")]
+use core::mem;
+#[cfg(feature = "alloc")]
+use core::ops::Index;
+#[cfg(feature = "alloc")]
+use core::ptr;
+use core::slice;
+
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "std")]
+pub mod adaptive;
+pub mod array;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub mod de;
+#[cfg(feature = "alloc")]
+mod entry;
+#[cfg(feature = "alloc")]
+pub mod fingerprint;
+#[cfg(feature = "alloc")]
+pub mod frozen;
+mod inline_vec;
+#[cfg(feature = "alloc")]
+pub mod int;
+#[cfg(feature = "alloc")]
+pub mod self_organizing;
+#[cfg(feature = "alloc")]
+pub mod set;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+mod ser;
+#[cfg(feature = "alloc")]
+mod simd;
+#[cfg(feature = "alloc")]
+pub mod small;
+#[cfg(feature = "alloc")]
+pub mod sorted;
+pub mod static_map;
 mod tests;
+pub mod view;
+
+#[cfg(feature = "std")]
+pub use adaptive::AdaptiveMap;
+pub use array::ArrayLinearMap;
+#[cfg(feature = "alloc")]
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+#[cfg(feature = "alloc")]
+pub use fingerprint::FingerprintLinearMap;
+#[cfg(feature = "alloc")]
+pub use frozen::FrozenLinearMap;
+#[cfg(feature = "alloc")]
+pub use int::{IntKey, IntLinearMap};
+#[cfg(feature = "alloc")]
+pub use self_organizing::SelfOrganizingLinearMap;
+#[cfg(feature = "alloc")]
+pub use set::LinearSet;
+#[cfg(feature = "alloc")]
+pub use small::SmallLinearMap;
+#[cfg(feature = "alloc")]
+pub use sorted::SortedLinearMap;
+pub use static_map::StaticLinearMap;
+pub use view::{LinearMapMut, LinearMapRef};
+
+#[cfg(feature = "alloc")]
+use fingerprint::FxBuildHasher;
+#[cfg(feature = "alloc")]
+use frozen::{Hashed, Sorted};
 
 /// LinearMap is a map that is implemented using arrays. The elements are stored unsorted
 /// which has the consequence that every operation takes at least O(n) time. Therefore, 
@@ -37,8 +156,34 @@ mod tests;
 ///
 /// To provide good interchangeability between maps, LinearMap provides the most important
 /// subset of methods which are also provided by [`BTreeMap`] and [`HashMap`]. Parts of the API
-/// requiring ordering are excluded, like `range` and `range_mut` from [`BTreeMap`]. 
-/// The `Entry API` is also excluded, however might be implemented later.
+/// requiring ordering are excluded, like `range` and `range_mut` from [`BTreeMap`]. They
+/// are provided by [`SortedLinearMap`](struct.SortedLinearMap.html) for `Ord` keys.
+/// The `Entry API` is provided through [`entry`](#method.entry) and allows to inspect and
+/// modify an entry with a single search through the keys.
+///
+/// # Ordering
+///
+/// The entries are stored in the order in which their keys were first inserted, unless
+/// they are moved by one of the methods below. Replacing the value of an existing key does
+/// not change its position. `iter`, `iter_mut`, `keys`, `values`, `values_mut` and
+/// `into_iter` all visit the entries in this order.
+///
+/// Removing an entry with [`shift_remove`](#method.shift_remove) or
+/// [`shift_remove_index`](#method.shift_remove_index) preserves the order of the remaining
+/// entries at the cost of moving all entries after it. [`remove`](#method.remove),
+/// [`swap_remove`](#method.swap_remove), [`remove_index`](#method.remove_index) and
+/// [`swap_remove_index`](#method.swap_remove_index) instead move the last entry into the
+/// place of the removed one, which takes constant time after the key has been found. Use
+/// the shifting variants when LinearMap is used as an insertion-ordered map.
+///
+/// The positions of entries can also be changed explicitly:
+///
+/// * [`swap_indices`](#method.swap_indices) and [`move_index`](#method.move_index) move
+///   single entries.
+/// * [`sort_keys`](#method.sort_keys), [`sort_by`](#method.sort_by) and
+///   [`sort_unstable_by_key`](#method.sort_unstable_by_key) sort all entries, for example
+///   to produce deterministic output.
+/// * [`reverse`](#method.reverse) reverses the order of all entries.
 ///
 /// [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
 /// [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
@@ -80,6 +225,8 @@ mod tests;
 /// }
 ///
 /// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
 pub struct LinearMap<K, V> 
     where K: PartialEq
 {
@@ -87,6 +234,7 @@ pub struct LinearMap<K, V>
     values: Vec<V>,
 }
 
+#[cfg(feature = "alloc")]
 impl<K, V> LinearMap<K, V> 
     where K: PartialEq
 {
@@ -103,7 +251,19 @@ impl<K, V> LinearMap<K, V>
     /// 
     /// let mut map: LinearMap<usize, &str> = LinearMap::new();
     /// ```
-    pub fn new() -> Self {
+    ///
+    /// As the function is `const`, it can initialize statics:
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    /// use std::sync::Mutex;
+    ///
+    /// static REGISTRY: Mutex<LinearMap<&str, u32>> = Mutex::new(LinearMap::new());
+    ///
+    /// REGISTRY.lock().unwrap().insert("a", 1);
+    /// ```
+    pub const fn new() -> Self {
         LinearMap {
             keys: Vec::new(),
             values: Vec::new(),
@@ -130,6 +290,46 @@ impl<K, V> LinearMap<K, V>
         }
     }
 
+    /// Creates a map from a vector of keys and a vector of values, where `values[i]` belongs
+    /// to `keys[i]`. The vectors are used as the storage of the map without copying them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error that gives back both vectors if they have different lengths or if
+    /// a key occurs more than once.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n²) where n is the number of keys, to check that the keys are unique.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::{FromPartsErrorKind, LinearMap};
+    ///
+    /// let map = LinearMap::from_parts(vec!["a", "b"], vec![1, 2]).unwrap();
+    /// assert_eq!(map["b"], 2);
+    ///
+    /// let error = LinearMap::from_parts(vec!["a", "b", "a"], vec![1, 2, 3]).unwrap_err();
+    /// assert_eq!(error.kind(), FromPartsErrorKind::DuplicateKey { index: 2 });
+    /// let (keys, values) = error.into_parts();
+    /// assert_eq!(keys.len(), values.len());
+    /// ```
+    pub fn from_parts(keys: Vec<K>, values: Vec<V>) -> Result<Self, FromPartsError<K, V>> {
+        let kind = if keys.len() != values.len() {
+            Some(FromPartsErrorKind::LengthMismatch)
+        } else {
+            (1..keys.len())
+                .find(|&i| find(&keys[..i], &keys[i]).is_some())
+                .map(|index| FromPartsErrorKind::DuplicateKey { index })
+        };
+        match kind {
+            Some(kind) => Err(FromPartsError { keys, values, kind }),
+            None => Ok(LinearMap { keys, values }),
+        }
+    }
+
     /// Clears the map, removing all key-value pairs. Keeps the allocated memory for reuse.
     ///
     /// # Time Complexity
@@ -213,6 +413,86 @@ impl<K, V> LinearMap<K, V>
         }
     }
 
+    /// Returns mutable references to the values of several keys at once, or `None` if any
+    /// of the keys is missing or if two of them refer to the same entry.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n * N + N²) where n is the number of elements in the map and N is the number of
+    /// keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut balances = LinearMap::new();
+    /// balances.insert("alice", 100);
+    /// balances.insert("bob", 20);
+    ///
+    /// if let Some([from, to]) = balances.get_many_mut(["alice", "bob"]) {
+    ///     *from -= 30;
+    ///     *to += 30;
+    /// }
+    /// assert_eq!(balances["alice"], 70);
+    /// assert_eq!(balances["bob"], 50);
+    ///
+    /// assert_eq!(balances.get_many_mut(["alice", "alice"]), None);
+    /// assert_eq!(balances.get_many_mut(["alice", "carol"]), None);
+    /// ```
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        let indices = self.find_many(keys)?;
+        for (i, index) in indices.iter().enumerate() {
+            if indices[..i].contains(index) {
+                return None;
+            }
+        }
+        // The indices are in bounds and distinct.
+        unsafe { Some(self.values_at_mut(indices)) }
+    }
+
+    /// Returns mutable references to the values of several keys at once, or `None` if any
+    /// of the keys is missing, without checking that the keys refer to different entries.
+    ///
+    /// # Safety
+    ///
+    /// No two of the keys may refer to the same entry of the map, as that would create
+    /// aliasing mutable references. Use [`get_many_mut`](#method.get_many_mut) to check
+    /// this.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n * N) where n is the number of elements in the map and N is the number of keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    ///
+    /// let [a, b] = unsafe { map.get_many_unchecked_mut([&0, &1]).unwrap() };
+    /// std::mem::swap(a, b);
+    /// assert_eq!(map[&0], "b");
+    /// ```
+    pub unsafe fn get_many_unchecked_mut<Q, const N: usize>(&mut self, keys: [&Q; N])
+        -> Option<[&mut V; N]>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        let indices = self.find_many(keys)?;
+        Some(self.values_at_mut(indices))
+    }
+
     /// Returns a tuple with references to the requested key and value when available.
     /// 
     /// # Time Complexity
@@ -243,11 +523,23 @@ impl<K, V> LinearMap<K, V>
         }
     }
 
-    /// Moves all values from `other` into `self`.
+    /// Moves all values from `other` into `self`, leaving `other` empty.
+    ///
+    /// If a key is present in both maps, the value from `other` replaces the value in
+    /// `self`. Since the keys of `other` are known to be unique, they are only compared
+    /// against the entries that were in `self` before the call. When `self` is empty, the
+    /// storage of `other` is taken over without moving any entries.
+    ///
+    /// If comparing two keys panics, `other` is left empty and `self` contains the entries
+    /// that were moved until then.
     /// 
     /// # Time Complexity
     ///
-    /// O(n^2) where n is the number of elements in the map.
+    /// O(n * m) where n is the number of elements in `self` and m is the number of
+    /// elements in `other`. A faster merge is not possible with `K: PartialEq` alone:
+    /// without `Hash` or `Ord`, the only way to find out whether a key of `other` is in
+    /// `self` is to compare it with every original entry. Appending to an empty map or
+    /// appending an empty map takes O(1).
     ///
     /// # Examples
     /// 
@@ -268,42 +560,69 @@ impl<K, V> LinearMap<K, V>
     /// assert_eq!(map1.get(&0), Some(&"a"));
     /// assert_eq!(map1.get(&1), Some(&"c")); // Value from map2 survived
     /// assert_eq!(map1.get(&2), Some(&"d"));
+    /// assert!(map2.is_empty());
     /// ```
     pub fn append(&mut self, other: &mut LinearMap<K, V>) {
-        for i in 0..other.len() {
-            unsafe {
-                let mut key = mem::uninitialized();
-                let mut value = mem::uninitialized();
-                mem::swap(&mut key, &mut other.keys[i]);
-                mem::swap(&mut value, &mut other.values[i]);
-                self.insert(key, value);
+        if self.is_empty() {
+            mem::swap(self, other);
+            return;
+        }
+        if other.is_empty() {
+            return;
+        }
+
+        let len = self.len();
+        self.keys.reserve(other.len());
+        self.values.reserve(other.len());
+
+        // Dropping the drains on unwind drops the remaining entries of `other` together,
+        // so both maps stay consistent if `PartialEq` panics.
+        for (key, value) in other.keys.drain(..).zip(other.values.drain(..)) {
+            if let Some(i) = find(&self.keys[..len], &key) {
+                self.values[i] = value;
+            } else {
+                self.keys.push(key);
+                self.values.push(value);
             }
         }
-        other.clear();
     }
 
-    /// Returns the number of elements the map can hold without reallocating.
+    /// Splits the map into two at the given position in
+    /// [storage order](struct.LinearMap.html#ordering). Returns a new map with the entries
+    /// `[at, len)`, and leaves the entries `[0, at)` in `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n - at) where n is the number of elements in the map.
     ///
     /// # Examples
     ///
     /// ```
     /// extern crate linear_map;
-    /// use linear_map::LinearMap; 
+    /// use linear_map::LinearMap;
     ///
-    /// assert_eq!(LinearMap::<i32, &str>::new().capacity(), 0);
-    /// assert_eq!(LinearMap::<i32, &str>::with_capacity(100).capacity(), 100);
+    /// let mut map: LinearMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+    /// let tail = map.split_off(1);
+    /// assert!(map.keys().eq(&[1]));
+    /// assert!(tail.keys().eq(&[2, 3]));
     /// ```
-    pub fn capacity(&self) -> usize {
-        self.keys.capacity()
+    pub fn split_off(&mut self, at: usize) -> LinearMap<K, V> {
+        LinearMap {
+            keys: self.keys.split_off(at),
+            values: self.values.split_off(at),
+        }
     }
 
-    /// Inserts a key-value pair into the map.
-    /// 
-    /// If the map did not have this key present, `None` is returned.
-    /// 
-    /// If the map did have this key present, the value is updated, and the old value 
-    /// is returned. The key is not updated, though; matters for types that can be == 
-    /// without being identical.
+    /// Retains only the entries for which the predicate returns `true` and removes the
+    /// others. The predicate receives a mutable reference to the value, like the one of
+    /// [`retain_mut`](#method.retain_mut). The retained entries keep their order.
+    ///
+    /// If the predicate panics, the entries that it has not been called for yet are
+    /// retained, together with the entry for which it panicked.
     ///
     /// # Time Complexity
     ///
@@ -313,94 +632,124 @@ impl<K, V> LinearMap<K, V>
     ///
     /// ```
     /// extern crate linear_map;
-    /// use linear_map::LinearMap; 
-    ///
-    /// let mut map = LinearMap::new();
-    /// assert_eq!(map.insert(0, "a"), None);
-    /// assert_eq!(map.insert(1, "b"), None);
-    /// assert_eq!(map.insert(1, "c"), Some("b"));
+    /// use linear_map::LinearMap;
     ///
+    /// let mut map: LinearMap<_, _> = (0..6).map(|i| (i, i * 10)).collect();
+    /// map.retain(|&k, v| {
+    ///     *v += 1;
+    ///     k % 2 == 0
+    /// });
+    /// assert_eq!(map.into_iter().collect::<Vec<_>>(), [(0, 1), (2, 21), (4, 41)]);
     /// ```
-    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        if let Some(i) = self.find(&key) {
-            Some(mem::replace(&mut self.values[i], value))
-        } else {
-            self.keys.push(key);
-            self.values.push(value);
-            None
-        }
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&K, &mut V) -> bool
+    {
+        self.extract_if(|k, v| !f(k, v)).for_each(drop);
     }
 
-    /// Returns the number of elements in the map.
+    /// Retains only the entries for which the predicate returns `true` and removes the
+    /// others. This is the same as [`retain`](#method.retain), under the name used by
+    /// `Vec::retain_mut`.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
     ///
     /// # Examples
     ///
     /// ```
     /// extern crate linear_map;
-    /// use linear_map::LinearMap; 
+    /// use linear_map::LinearMap;
     ///
-    /// let mut map = LinearMap::new();
-    /// map.insert(0, "a");
-    /// map.insert(1, "b");
-    /// assert_eq!(map.len(), 2);
+    /// let mut map: LinearMap<_, _> = (0..4).map(|i| (i, i)).collect();
+    /// map.retain_mut(|_, v| {
+    ///     *v *= 3;
+    ///     *v < 6
+    /// });
+    /// assert_eq!(map.into_iter().collect::<Vec<_>>(), [(0, 0), (1, 3)]);
     /// ```
-    pub fn len(&self) -> usize {
-        self.keys.len()
+    pub fn retain_mut<F>(&mut self, f: F)
+        where F: FnMut(&K, &mut V) -> bool
+    {
+        self.retain(f);
     }
 
-    /// Returns if the map is empty.
+    /// Removes all entries from the map and returns them as an iterator in
+    /// [storage order](struct.LinearMap.html#ordering). Keeps the allocated memory for
+    /// reuse.
+    ///
+    /// The map is empty after the iterator is dropped, even if it was not fully consumed.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
     ///
     /// # Examples
     ///
     /// ```
     /// extern crate linear_map;
-    /// use linear_map::LinearMap; 
+    /// use linear_map::LinearMap;
     ///
-    /// let mut map = LinearMap::new();
+    /// let mut map = LinearMap::with_capacity(8);
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    ///
+    /// let pairs: Vec<_> = map.drain().collect();
+    /// assert_eq!(pairs, [(1, "a"), (2, "b")]);
     /// assert!(map.is_empty());
-    /// map.insert(0, "a");
-    /// assert!(!map.is_empty());
+    /// assert!(map.capacity() >= 8);
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.keys.is_empty()
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        Drain {
+            key: self.keys.drain(..),
+            value: self.values.drain(..),
+        }
     }
 
-    /// Removes the entry from the map.
+    /// Creates an iterator that visits the entries in
+    /// [storage order](struct.LinearMap.html#ordering), removes every entry for which the
+    /// predicate returns `true` and yields it. The remaining entries keep their order.
+    ///
+    /// Entries are only visited when the iterator is advanced. If it is dropped before it
+    /// is exhausted, the entries that have not been visited are retained. If the predicate
+    /// panics, the entry for which it panicked is retained as well.
     ///
     /// # Time Complexity
     ///
-    /// O(n) where n is the number of elements in the map.
+    /// O(n) where n is the number of elements in the map, for consuming the iterator.
     ///
     /// # Examples
     ///
     /// ```
     /// extern crate linear_map;
-    /// use linear_map::LinearMap; 
+    /// use linear_map::LinearMap;
     ///
-    /// let mut map = LinearMap::new();
-    /// map.insert(0, "a");
-    /// map.insert(1, "b");
-    /// map.remove(&0);
-    /// assert_eq!(map.len(), 1);
+    /// let mut map: LinearMap<_, _> = (0..6).map(|i| (i, i)).collect();
+    /// let odd: Vec<_> = map.extract_if(|k, _| k % 2 == 1).collect();
+    /// assert_eq!(odd, [(1, 1), (3, 3), (5, 5)]);
+    /// assert!(map.keys().eq(&[0, 2, 4]));
     /// ```
-    pub fn remove<Q>(&mut self, key: &Q) -> Option<V> 
-        where
-            K: Borrow<Q>,
-            Q: PartialEq + ?Sized, 
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, F>
+        where F: FnMut(&K, &mut V) -> bool
     {
-        if let Some(i) = self.find(key) {
-            self.keys.swap_remove(i);
-            Some(self.values.swap_remove(i))
-        } else {
-            None
+        let old_len = self.len();
+        // The lengths are restored by `ExtractIf::drop`. If the iterator is leaked, the
+        // entries are leaked as well instead of being dropped twice.
+        unsafe {
+            self.keys.set_len(0);
+            self.values.set_len(0);
+        }
+        ExtractIf {
+            map: self,
+            index: 0,
+            deleted: 0,
+            old_len,
+            pred,
         }
     }
 
-    /// Returns `true` if the map contains a value for the specified key.
-    ///
-    /// # Time Complexity
-    ///
-    /// O(n) where n is the number of elements in the map.
+    /// Returns the number of elements the map can hold without reallocating, which is the
+    /// smaller capacity of the vectors that store the keys and the values.
     ///
     /// # Examples
     ///
@@ -408,146 +757,1428 @@ impl<K, V> LinearMap<K, V>
     /// extern crate linear_map;
     /// use linear_map::LinearMap; 
     ///
-    /// let mut map = LinearMap::new();
-    /// map.insert(0, "a");
-    /// assert!(map.contains_key(&0));
-    /// assert!(!map.contains_key(&1));
+    /// assert_eq!(LinearMap::<i32, &str>::new().capacity(), 0);
+    /// assert_eq!(LinearMap::<i32, &str>::with_capacity(100).capacity(), 100);
     /// ```
-    pub fn contains_key<Q>(&self, key: &Q) -> bool 
-        where
-            K: Borrow<Q>,
-            Q: PartialEq + ?Sized,
-    {
-        self.find(key) != None
+    pub fn capacity(&self) -> usize {
+        cmp::min(self.keys.capacity(), self.values.capacity())
     }
 
-    /// Gets an iterator over the keys of the map, unsorted.
+    /// Reserves capacity for at least `additional` more elements. The map may reserve more
+    /// space to avoid frequent reallocations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows `isize::MAX` bytes.
     ///
     /// # Examples
     ///
     /// ```
     /// extern crate linear_map;
-    /// use linear_map::LinearMap; 
+    /// use linear_map::LinearMap;
     ///
-    /// let mut map = LinearMap::new();
-    /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// map.insert(3, "c");
-    /// 
-    /// for key in map.keys() {
-    ///     println!("{}", key);
-    /// }
+    /// let mut map: LinearMap<i32, &str> = LinearMap::new();
+    /// map.reserve(10);
+    /// assert!(map.capacity() >= 10);
     /// ```
-    pub fn keys(&self) -> slice::Iter<K> {
-        self.keys.iter()
+    pub fn reserve(&mut self, additional: usize) {
+        self.keys.reserve(additional);
+        self.values.reserve(additional);
     }
 
-    /// Gets an iterator over the values of the map, unsorted.
+    /// Reserves capacity for exactly `additional` more elements, unless the capacity is
+    /// already sufficient. The allocator may still give the map more space than requested.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows `isize::MAX` bytes.
     ///
     /// # Examples
     ///
     /// ```
     /// extern crate linear_map;
-    /// use linear_map::LinearMap; 
+    /// use linear_map::LinearMap;
     ///
-    /// let mut map = LinearMap::new();
-    /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// map.insert(3, "c");
-    /// 
-    /// for value in map.values() {
-    ///     println!("{}", value);
-    /// }
+    /// let mut map: LinearMap<i32, &str> = LinearMap::new();
+    /// map.reserve_exact(10);
+    /// assert!(map.capacity() >= 10);
     /// ```
-    pub fn values(&self) -> slice::Iter<V> {
-        self.values.iter()
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.keys.reserve_exact(additional);
+        self.values.reserve_exact(additional);
     }
 
-    /// Gets a mutable iterator over the values of the map, unsorted.
+    /// Tries to reserve capacity for at least `additional` more elements, like
+    /// [`reserve`](#method.reserve).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the capacity overflows or the allocator reports a failure. The
+    /// capacity of the map is unchanged in that case.
     ///
     /// # Examples
     ///
     /// ```
     /// extern crate linear_map;
-    /// use linear_map::LinearMap; 
-    ///
-    /// let mut map = LinearMap::new();
-    /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// map.insert(3, "c");
-    /// 
-    /// for value in map.values_mut() {
-    ///     *value = "d";
-    /// }
+    /// use linear_map::LinearMap;
     ///
-    /// assert!(map.values().all(|v| *v == "d"));
+    /// let mut map: LinearMap<u64, u64> = LinearMap::new();
+    /// assert!(map.try_reserve(10).is_ok());
+    /// assert!(map.try_reserve(usize::MAX).is_err());
+    /// assert!(map.capacity() >= 10);
     /// ```
-    pub fn values_mut(&mut self) -> slice::IterMut<V> {
-        self.values.iter_mut()
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let capacity = self.keys.capacity();
+        self.keys.try_reserve(additional)?;
+        self.values.try_reserve(additional).inspect_err(|_| self.keys.shrink_to(capacity))
+    }
+
+    /// Tries to reserve capacity for exactly `additional` more elements, like
+    /// [`reserve_exact`](#method.reserve_exact).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the capacity overflows or the allocator reports a failure. The
+    /// capacity of the map is unchanged in that case.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let capacity = self.keys.capacity();
+        self.keys.try_reserve_exact(additional)?;
+        self.values.try_reserve_exact(additional).inspect_err(|_| self.keys.shrink_to(capacity))
     }
 
-    /// Gets an iterator over the entries of the map, unsorted.
+    /// Shrinks the capacity of the map as much as possible.
     ///
     /// # Examples
     ///
     /// ```
     /// extern crate linear_map;
-    /// use linear_map::LinearMap; 
+    /// use linear_map::LinearMap;
     ///
-    /// let mut map = LinearMap::new();
+    /// let mut map = LinearMap::with_capacity(100);
     /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// map.insert(3, "c");
-    /// 
-    /// for (key, value) in map.iter() {
-    ///     println!("{}: {}", key, value);
-    /// }
+    /// map.shrink_to_fit();
+    /// assert!(map.capacity() >= 1);
+    /// assert!(map.capacity() < 100);
     /// ```
-    pub fn iter(&self) -> Iter<K, V> {
-        Iter {
-            key: self.keys.iter(),
-            value: self.values.iter(),
-        }
+    pub fn shrink_to_fit(&mut self) {
+        self.keys.shrink_to_fit();
+        self.values.shrink_to_fit();
     }
 
-    /// Gets a mutable iterator over the entries of the map, unsorted.
+    /// Shrinks the capacity of the map to at least `min_capacity`, or to its length if that
+    /// is larger. Does nothing if the capacity is already smaller.
     ///
     /// # Examples
     ///
     /// ```
     /// extern crate linear_map;
-    /// use linear_map::LinearMap; 
+    /// use linear_map::LinearMap;
     ///
-    /// let mut map = LinearMap::new();
+    /// let mut map = LinearMap::with_capacity(100);
     /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// map.insert(3, "c");
-    /// 
-    /// for (_key, value) in map.iter_mut() {
-    ///     *value = "d";
-    /// }
-    ///
-    /// assert!(map.values().all(|v| *v == "d"));
+    /// map.shrink_to(10);
+    /// assert!(map.capacity() >= 10);
+    /// assert!(map.capacity() < 100);
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<K, V> {
-        IterMut {
-            key: self.keys.iter(),
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.keys.shrink_to(min_capacity);
+        self.values.shrink_to(min_capacity);
+    }
+
+    /// Inserts a key-value pair into the map.
+    /// 
+    /// If the map did not have this key present, `None` is returned.
+    /// 
+    /// If the map did have this key present, the value is updated, and the old value 
+    /// is returned. The key is not updated, though; matters for types that can be == 
+    /// without being identical.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// assert_eq!(map.insert(0, "a"), None);
+    /// assert_eq!(map.insert(1, "b"), None);
+    /// assert_eq!(map.insert(1, "c"), Some("b"));
+    ///
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(i) = self.find(&key) {
+            Some(mem::replace(&mut self.values[i], value))
+        } else {
+            self.keys.push(key);
+            self.values.push(value);
+            None
+        }
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut letters = LinearMap::new();
+    ///
+    /// for ch in "a short treatise on fungi".chars() {
+    ///     let counter = letters.entry(ch).or_insert(0);
+    ///     *counter += 1;
+    /// }
+    ///
+    /// assert_eq!(letters.get(&'s'), Some(&2));
+    /// assert_eq!(letters.get(&'t'), Some(&3));
+    /// assert_eq!(letters.get(&'u'), Some(&1));
+    /// assert_eq!(letters.get(&'y'), None);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        Entry::new(self, key)
+    }
+
+    /// Returns the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns if the map is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// assert!(map.is_empty());
+    /// map.insert(0, "a");
+    /// assert!(!map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Removes the entry from the map and returns its value.
+    ///
+    /// This is equivalent to [`swap_remove`](#method.swap_remove): the last entry of the
+    /// map takes the place of the removed one. Use [`shift_remove`](#method.shift_remove) to
+    /// preserve the order of the remaining entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// map.remove(&0);
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V> 
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized, 
+    {
+        self.swap_remove(key)
+    }
+
+    /// Removes the entry from the map and returns its value. The last entry of the map
+    /// takes the place of the removed one, which changes the order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// map.insert(2, "c");
+    /// assert_eq!(map.swap_remove(&0), Some("a"));
+    /// assert!(map.keys().eq(&[2, 1]));
+    /// ```
+    pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V> 
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized, 
+    {
+        self.swap_remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the entry from the map and returns the stored key and value. The last entry
+    /// of the map takes the place of the removed one, which changes the order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// map.insert(2, "c");
+    /// assert_eq!(map.swap_remove_entry(&0), Some((0, "a")));
+    /// assert!(map.keys().eq(&[2, 1]));
+    /// ```
+    pub fn swap_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)> 
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized, 
+    {
+        if let Some(i) = self.find(key) {
+            let key = self.keys.swap_remove(i);
+            Some((key, self.values.swap_remove(i)))
+        } else {
+            None
+        }
+    }
+
+    /// Removes the entry from the map and returns its value. All entries after the removed
+    /// one are moved forward, which preserves the order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// map.insert(2, "c");
+    /// assert_eq!(map.shift_remove(&0), Some("a"));
+    /// assert!(map.keys().eq(&[1, 2]));
+    /// ```
+    pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V> 
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized, 
+    {
+        self.shift_remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the entry from the map and returns the stored key and value. All entries
+    /// after the removed one are moved forward, which preserves the order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// map.insert(2, "c");
+    /// assert_eq!(map.shift_remove_entry(&0), Some((0, "a")));
+    /// assert!(map.keys().eq(&[1, 2]));
+    /// ```
+    pub fn shift_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)> 
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized, 
+    {
+        if let Some(i) = self.find(key) {
+            let key = self.keys.remove(i);
+            Some((key, self.values.remove(i)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the position of the key in [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// assert_eq!(map.get_index_of("b"), Some(1));
+    /// assert_eq!(map.get_index_of("c"), None);
+    /// ```
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.find(key)
+    }
+
+    /// Returns the entry at `index` in [storage order](struct.LinearMap.html#ordering), or
+    /// `None` if `index` is out of bounds.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// assert_eq!(map.get_index(1), Some((&"b", &2)));
+    /// assert_eq!(map.get_index(2), None);
+    /// ```
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        if index < self.len() {
+            Some((&self.keys[index], &self.values[index]))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the entry at `index` in [storage order](struct.LinearMap.html#ordering) with
+    /// a mutable reference to the value, or `None` if `index` is out of bounds.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("a", 1);
+    /// if let Some((_, value)) = map.get_index_mut(0) {
+    ///     *value = 10;
+    /// }
+    /// assert_eq!(map["a"], 10);
+    /// ```
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        if index < self.len() {
+            Some((&self.keys[index], &mut self.values[index]))
+        } else {
+            None
+        }
+    }
+
+    /// Removes the entry at `index` and returns it, or returns `None` if `index` is out of
+    /// bounds.
+    ///
+    /// This is equivalent to [`swap_remove_index`](#method.swap_remove_index): the last
+    /// entry of the map takes the place of the removed one. Use
+    /// [`shift_remove_index`](#method.shift_remove_index) to preserve the order of the
+    /// remaining entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// map.insert(2, "c");
+    /// assert_eq!(map.remove_index(0), Some((0, "a")));
+    /// assert!(map.keys().eq(&[2, 1]));
+    /// ```
+    pub fn remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        self.swap_remove_index(index)
+    }
+
+    /// Removes the entry at `index` and returns it, or returns `None` if `index` is out of
+    /// bounds. The last entry of the map takes the place of the removed one, which changes
+    /// the order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    pub fn swap_remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        if index < self.len() {
+            let key = self.keys.swap_remove(index);
+            Some((key, self.values.swap_remove(index)))
+        } else {
+            None
+        }
+    }
+
+    /// Removes the entry at `index` and returns it, or returns `None` if `index` is out of
+    /// bounds. All entries after the removed one are moved forward, which preserves the
+    /// order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// map.insert(2, "c");
+    /// assert_eq!(map.shift_remove_index(0), Some((0, "a")));
+    /// assert!(map.keys().eq(&[1, 2]));
+    /// ```
+    pub fn shift_remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        if index < self.len() {
+            let key = self.keys.remove(index);
+            Some((key, self.values.remove(index)))
+        } else {
+            None
+        }
+    }
+
+    /// Swaps the positions of the entries at `a` and `b` in
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// map.swap_indices(0, 1);
+    /// assert!(map.keys().eq(&[1, 0]));
+    /// assert_eq!(map[&0], "a");
+    /// ```
+    pub fn swap_indices(&mut self, a: usize, b: usize) {
+        self.keys.swap(a, b);
+        self.values.swap(a, b);
+    }
+
+    /// Moves the entry at `from` to the position `to` in
+    /// [storage order](struct.LinearMap.html#ordering). The entries in between are shifted
+    /// by one position to make room.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` are out of bounds.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(|from - to|)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map: LinearMap<_, _> = (0..5).map(|i| (i, i)).collect();
+    /// map.move_index(3, 1);
+    /// assert!(map.keys().eq(&[0, 3, 1, 2, 4]));
+    /// map.move_index(1, 4);
+    /// assert!(map.keys().eq(&[0, 1, 2, 4, 3]));
+    /// ```
+    pub fn move_index(&mut self, from: usize, to: usize) {
+        let len = self.len();
+        assert!(from < len && to < len,
+                "index out of bounds: the len is {} but the indices are {} and {}", len, from, to);
+        if from < to {
+            self.keys[from..=to].rotate_left(1);
+            self.values[from..=to].rotate_left(1);
+        } else {
+            self.keys[to..=from].rotate_right(1);
+            self.values[to..=from].rotate_right(1);
+        }
+    }
+
+    /// Sorts the entries of the map by key, which changes their
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n log n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("c", 3);
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.sort_keys();
+    /// assert!(map.keys().eq(&["a", "b", "c"]));
+    /// assert!(map.values().eq(&[1, 2, 3]));
+    /// ```
+    pub fn sort_keys(&mut self)
+        where K: Ord
+    {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_unstable_by(|&a, &b| self.keys[a].cmp(&self.keys[b]));
+        self.permute(order);
+    }
+
+    /// Sorts the entries of the map with a comparison function that is given the key and
+    /// value of both entries.
+    ///
+    /// The sort is stable, so entries that compare equal keep their relative order. If the
+    /// comparison function panics, the map is left unchanged.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n log n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut scores = LinearMap::new();
+    /// scores.insert("carol", 7);
+    /// scores.insert("alice", 9);
+    /// scores.insert("bob", 7);
+    /// scores.sort_by(|_, v1, _, v2| v2.cmp(v1));
+    /// assert!(scores.keys().eq(&["alice", "carol", "bob"]));
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F)
+        where F: FnMut(&K, &V, &K, &V) -> cmp::Ordering
+    {
+        let (keys, values) = (&self.keys, &self.values);
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by(|&a, &b| compare(&keys[a], &values[a], &keys[b], &values[b]));
+        self.permute(order);
+    }
+
+    /// Sorts the entries of the map by a sort key that is extracted from the key and value of
+    /// every entry.
+    ///
+    /// The sort is unstable, so entries with equal sort keys may end up in any order. If the
+    /// extraction function panics, the map is left unchanged.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n log n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut sizes = LinearMap::new();
+    /// sizes.insert("b.txt", 20);
+    /// sizes.insert("a.txt", 30);
+    /// sizes.insert("c.txt", 10);
+    /// sizes.sort_unstable_by_key(|_, &size| size);
+    /// assert!(sizes.keys().eq(&["c.txt", "b.txt", "a.txt"]));
+    /// ```
+    pub fn sort_unstable_by_key<T, F>(&mut self, mut f: F)
+        where
+            T: Ord,
+            F: FnMut(&K, &V) -> T,
+    {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_unstable_by_key(|&i| f(&self.keys[i], &self.values[i]));
+        self.permute(order);
+    }
+
+    /// Reverses the [storage order](struct.LinearMap.html#ordering) of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map: LinearMap<_, _> = (0..3).map(|i| (i, i * 10)).collect();
+    /// map.reverse();
+    /// assert!(map.keys().eq(&[2, 1, 0]));
+    /// assert!(map.values().eq(&[20, 10, 0]));
+    /// ```
+    pub fn reverse(&mut self) {
+        self.keys.reverse();
+        self.values.reverse();
+    }
+
+    /// Moves the entries so that `order[i]` is the old position of the entry at `i`. The
+    /// sorting methods sort the positions first, so the map is unchanged if they panic.
+    fn permute(&mut self, mut order: Vec<usize>) {
+        // Every cycle of the permutation is applied with swaps, and visited positions are
+        // marked by pointing them at themselves.
+        for start in 0..order.len() {
+            let mut i = start;
+            while order[i] != start {
+                let j = order[i];
+                self.keys.swap(i, j);
+                self.values.swap(i, j);
+                order[i] = i;
+                i = j;
+            }
+            order[i] = i;
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(0, "a");
+    /// assert!(map.contains_key(&0));
+    /// assert!(!map.contains_key(&1));
+    /// ```
+    pub fn contains_key<Q>(&self, key: &Q) -> bool 
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Gets an iterator over the keys of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// 
+    /// for key in map.keys() {
+    ///     println!("{}", key);
+    /// }
+    /// ```
+    pub fn keys(&self) -> Keys<'_, K> {
+        Keys { iter: self.keys.iter() }
+    }
+
+    /// Gets an iterator over the values of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// 
+    /// for value in map.values() {
+    ///     println!("{}", value);
+    /// }
+    /// ```
+    pub fn values(&self) -> Values<'_, V> {
+        Values { iter: self.values.iter() }
+    }
+
+    /// Gets a mutable iterator over the values of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// 
+    /// for value in map.values_mut() {
+    ///     *value = "d";
+    /// }
+    ///
+    /// assert!(map.values().all(|v| *v == "d"));
+    /// ```
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        ValuesMut { iter: self.values.iter_mut() }
+    }
+
+    /// Returns the keys of the map as a slice in
+    /// [storage order](struct.LinearMap.html#ordering). The value of `keys_slice()[i]` is
+    /// `values_slice()[i]`.
+    ///
+    /// There is no mutable counterpart, as modifying the keys could make two of them equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// assert_eq!(map.keys_slice(), ["a", "b"]);
+    /// ```
+    pub fn keys_slice(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Returns the values of the map as a slice in
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("a", 1.0);
+    /// map.insert("b", 2.0);
+    /// let sum: f64 = map.values_slice().iter().sum();
+    /// assert_eq!(sum, 3.0);
+    /// ```
+    pub fn values_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Returns the values of the map as a mutable slice in
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("a", 1.0);
+    /// map.insert("b", 2.0);
+    /// for value in map.values_mut_slice() {
+    ///     *value *= 0.5;
+    /// }
+    /// assert_eq!(map.values_slice(), [0.5, 1.0]);
+    /// ```
+    pub fn values_mut_slice(&mut self) -> &mut [V] {
+        &mut self.values
+    }
+
+    /// Gets an iterator over the entries of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// 
+    /// for (key, value) in map.iter() {
+    ///     println!("{}: {}", key, value);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            key: self.keys.iter(),
+            value: self.values.iter(),
+        }
+    }
+
+    /// Gets a mutable iterator over the entries of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap; 
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// 
+    /// for (_key, value) in map.iter_mut() {
+    ///     *value = "d";
+    /// }
+    ///
+    /// assert!(map.values().all(|v| *v == "d"));
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            key: self.keys.iter(),
             value: self.values.iter_mut(),
         }
     }
-    
-    #[inline]
-    fn find<Q>(&self, key: &Q) -> Option<usize>
-        where
-            K: Borrow<Q>,
-            Q: PartialEq + ?Sized
-    {
-        for (i, k) in self.keys.iter().enumerate() {
-            if key.eq(k.borrow()) {
-                return Some(i);
-            }
+
+    /// Creates a consuming iterator over the keys of the map in
+    /// [storage order](struct.LinearMap.html#ordering). The values are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("a".to_string(), 1);
+    /// map.insert("b".to_string(), 2);
+    /// let keys: Vec<String> = map.into_keys().collect();
+    /// assert_eq!(keys, ["a", "b"]);
+    /// ```
+    pub fn into_keys(self) -> IntoKeys<K> {
+        IntoKeys { iter: self.keys.into_iter() }
+    }
+
+    /// Creates a consuming iterator over the values of the map in
+    /// [storage order](struct.LinearMap.html#ordering). The keys are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(1, "a".to_string());
+    /// map.insert(2, "b".to_string());
+    /// let values: Vec<String> = map.into_values().collect();
+    /// assert_eq!(values, ["a", "b"]);
+    /// ```
+    pub fn into_values(self) -> IntoValues<V> {
+        IntoValues { iter: self.values.into_iter() }
+    }
+
+    /// Splits the map into the vector of keys and the vector of values in
+    /// [storage order](struct.LinearMap.html#ordering), where `values[i]` belongs to
+    /// `keys[i]`. [`from_parts`](#method.from_parts) is the inverse.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("x", 1.5);
+    /// map.insert("y", 2.5);
+    /// let (keys, mut values) = map.into_parts();
+    /// for value in &mut values {
+    ///     *value *= 2.0;
+    /// }
+    /// let map = LinearMap::from_parts(keys, values).unwrap();
+    /// assert_eq!(map["y"], 5.0);
+    /// ```
+    pub fn into_parts(self) -> (Vec<K>, Vec<V>) {
+        (self.keys, self.values)
+    }
+
+    /// Borrows the keys and values as a read-only [`LinearMapRef`](struct.LinearMapRef.html)
+    /// without copying them.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::{LinearMap, LinearMapRef};
+    ///
+    /// fn total(prices: LinearMapRef<&str, u32>) -> u32 {
+    ///     prices.values().sum()
+    /// }
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("apple", 3);
+    /// map.insert("pear", 4);
+    /// assert_eq!(total(map.as_view()), 7);
+    ///
+    /// let table = [("plum", 2)];
+    /// assert_eq!(total(LinearMapRef::from_pairs(&table)), 2);
+    /// ```
+    pub fn as_view(&self) -> LinearMapRef<'_, K, V> {
+        LinearMapRef::new(&self.keys, &self.values)
+    }
+
+    /// Borrows the keys and values as a [`LinearMapMut`](struct.LinearMapMut.html), which
+    /// can modify the values but not insert or remove entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("a", 1);
+    /// for (_, value) in map.as_view_mut() {
+    ///     *value *= 10;
+    /// }
+    /// assert_eq!(map["a"], 10);
+    /// ```
+    pub fn as_view_mut(&mut self) -> LinearMapMut<'_, K, V> {
+        LinearMapMut::new(&self.keys, &mut self.values)
+    }
+    
+    /// Converts the map into an immutable [`FrozenLinearMap`](struct.FrozenLinearMap.html)
+    /// that is arranged for the fastest lookups of `Ord` keys.
+    ///
+    /// Maps with more than eight entries are sorted by key and searched with binary search.
+    /// Smaller maps are searched linearly, which is faster for so few entries, but they are
+    /// sorted as well, so the entries of the frozen map are always in ascending order of
+    /// their keys. Keys that only implement `Hash` can be frozen with
+    /// [`freeze_hashed`](#method.freeze_hashed) instead.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n log n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let map: LinearMap<_, _> = vec![(3, "c"), (1, "a"), (2, "b")].into_iter().collect();
+    /// let map = map.freeze();
+    /// assert_eq!(map.get(&2), Some(&"b"));
+    /// assert!(map.keys().eq(&[1, 2, 3]));
+    /// ```
+    pub fn freeze(self) -> FrozenLinearMap<K, V, Sorted>
+        where K: Ord
+    {
+        FrozenLinearMap::sorted(self)
+    }
+
+    /// Converts the map into an immutable [`FrozenLinearMap`](struct.FrozenLinearMap.html)
+    /// that keeps the order of the entries and searches the keys linearly.
+    ///
+    /// Lookups are exactly as fast as in the `LinearMap`, because only the spare capacity is
+    /// dropped. This is meant for keys that implement neither `Ord` nor `Hash`; otherwise
+    /// [`freeze`](#method.freeze) or [`freeze_hashed`](#method.freeze_hashed) find keys
+    /// faster in larger maps.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map, to drop the spare capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert(1, "a");
+    /// let map = map.freeze_scan();
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// ```
+    pub fn freeze_scan(self) -> FrozenLinearMap<K, V> {
+        FrozenLinearMap::scan(self)
+    }
+
+    /// Converts the map into an immutable [`FrozenLinearMap`](struct.FrozenLinearMap.html)
+    /// that compares hash fingerprints before the keys, like
+    /// [`FingerprintLinearMap`](struct.FingerprintLinearMap.html).
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("a".to_string(), 1);
+    /// let map = map.freeze_hashed();
+    /// assert_eq!(map.get("a"), Some(&1));
+    /// ```
+    pub fn freeze_hashed(self) -> FrozenLinearMap<K, V, Hashed>
+        where K: Hash
+    {
+        FrozenLinearMap::hashed(self, FxBuildHasher)
+    }
+
+    /// Converts the map into an immutable [`FrozenLinearMap`](struct.FrozenLinearMap.html)
+    /// that compares hash fingerprints computed with `hash_builder` before the keys.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn freeze_hashed_with_hasher<S>(self, hash_builder: S) -> FrozenLinearMap<K, V, Hashed<S>>
+        where
+            K: Hash,
+            S: BuildHasher,
+    {
+        FrozenLinearMap::hashed(self, hash_builder)
+    }
+
+    #[inline]
+    pub(crate) fn find<Q>(&self, key: &Q) -> Option<usize>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized
+    {
+        find(&self.keys, key)
+    }
+
+    fn find_many<Q, const N: usize>(&self, keys: [&Q; N]) -> Option<[usize; N]>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        let mut indices = [0; N];
+        for (index, key) in indices.iter_mut().zip(keys.iter()) {
+            *index = self.find(*key)?;
+        }
+        Some(indices)
+    }
+
+    /// The indices must be in bounds and distinct.
+    unsafe fn values_at_mut<const N: usize>(&mut self, indices: [usize; N]) -> [&mut V; N] {
+        let values = self.values.as_mut_ptr();
+        indices.map(|i| &mut *values.add(i))
+    }
+}
+
+/// Returns the position of `key` in `keys` using a linear search.
+#[inline]
+pub(crate) fn find<K, Q>(keys: &[K], key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized
+{
+    for (i, k) in keys.iter().enumerate() {
+        if key.eq(k.borrow()) {
+            return Some(i);
+        }
+    }
+    None
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> Default for LinearMap<K, V> 
+    where K: PartialEq
+{
+    /// Creates an empty `LinearMap`.
+    fn default() -> Self {
+        LinearMap::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> fmt::Debug for LinearMap<K, V> 
+    where
+        K: PartialEq + fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Two maps are equal when they contain the same entries, regardless of the order in which
+/// the entries are stored.
+#[cfg(feature = "alloc")]
+impl<K, V> PartialEq for LinearMap<K, V> 
+    where
+        K: PartialEq,
+        V: PartialEq,
+{
+    fn eq(&self, other: &LinearMap<K, V>) -> bool {
+        self.len() == other.len() &&
+            self.iter().all(|(key, value)| other.get(key).is_some_and(|v| *value == *v))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> Eq for LinearMap<K, V> 
+    where
+        K: Eq,
+        V: Eq,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> FromIterator<(K, V)> for LinearMap<K, V> 
+    where K: PartialEq
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> LinearMap<K, V> {
+        let mut map = LinearMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+/// Inserts all entries of the iterator. Entries with a key that is already present replace
+/// the value in the map.
+#[cfg(feature = "alloc")]
+impl<K, V> Extend<(K, V)> for LinearMap<K, V> 
+    where K: PartialEq
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.keys.reserve(lower);
+        self.values.reserve(lower);
+        for (key, value) in iter {
+            self.insert(key, value);
         }
-        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> Extend<(&'a K, &'a V)> for LinearMap<K, V> 
+    where
+        K: PartialEq + Copy + 'a,
+        V: Copy + 'a,
+{
+    fn extend<T: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, Q, V> Index<&Q> for LinearMap<K, V> 
+    where
+        K: PartialEq + Borrow<Q>,
+        Q: PartialEq + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+/// An iterator over the keys of a LinearMap.
+///
+/// This struct is created by the `keys` method on [`LinearMap`](struct.LinearMap.html). See its documentation for more.
+pub struct Keys<'a, K: 'a> {
+    iter: slice::Iter<'a, K>,
+}
+
+impl<'a, K> Iterator for Keys<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K> DoubleEndedIterator for Keys<'a, K> {
+    fn next_back(&mut self) -> Option<&'a K> {
+        self.iter.next_back()
+    }
+}
+
+impl<'a, K> ExactSizeIterator for Keys<'a, K> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, K> FusedIterator for Keys<'a, K> {}
+
+impl<'a, K> Clone for Keys<'a, K> {
+    fn clone(&self) -> Self {
+        Keys { iter: self.iter.clone() }
+    }
+}
+
+impl<'a, K: fmt::Debug> fmt::Debug for Keys<'a, K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// An iterator over the values of a LinearMap.
+///
+/// This struct is created by the `values` method on [`LinearMap`](struct.LinearMap.html). See its documentation for more.
+pub struct Values<'a, V: 'a> {
+    iter: slice::Iter<'a, V>,
+}
+
+impl<'a, V> Iterator for Values<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for Values<'a, V> {
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.iter.next_back()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for Values<'a, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, V> FusedIterator for Values<'a, V> {}
+
+impl<'a, V> Clone for Values<'a, V> {
+    fn clone(&self) -> Self {
+        Values { iter: self.iter.clone() }
+    }
+}
+
+impl<'a, V: fmt::Debug> fmt::Debug for Values<'a, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// A mutable iterator over the values of a LinearMap.
+///
+/// This struct is created by the `values_mut` method on [`LinearMap`](struct.LinearMap.html). See its documentation for more.
+pub struct ValuesMut<'a, V: 'a> {
+    iter: slice::IterMut<'a, V>,
+}
+
+impl<'a, V> Iterator for ValuesMut<'a, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<&'a mut V> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for ValuesMut<'a, V> {
+    fn next_back(&mut self) -> Option<&'a mut V> {
+        self.iter.next_back()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for ValuesMut<'a, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, V> FusedIterator for ValuesMut<'a, V> {}
+
+impl<'a, V: fmt::Debug> fmt::Debug for ValuesMut<'a, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter.as_slice()).finish()
     }
 }
 
@@ -570,8 +2201,47 @@ impl<'a, K: 'a, V: 'a> Iterator for Iter<'a, K, V> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.key.size_hint()
+    }
+}
+
+impl<'a, K: 'a, V: 'a> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        if let Some(key) = self.key.next_back() {
+            let value = self.value.next_back().unwrap();
+            Some((key, value))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a> ExactSizeIterator for Iter<'a, K, V> {
+    fn len(&self) -> usize {
+        self.key.len()
+    }
+}
+
+impl<'a, K: 'a, V: 'a> FusedIterator for Iter<'a, K, V> {}
+
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    fn clone(&self) -> Self {
+        Iter {
+            key: self.key.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<'a, K: fmt::Debug, V: fmt::Debug> fmt::Debug for Iter<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, K, V> IntoIterator for &'a LinearMap<K, V> 
     where 
         K: PartialEq + 'a,
@@ -604,8 +2274,42 @@ impl<'a, K: 'a, V: 'a> Iterator for IterMut<'a, K, V> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.key.size_hint()
+    }
+}
+
+impl<'a, K: 'a, V: 'a> DoubleEndedIterator for IterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a mut V)> {
+        if let Some(key) = self.key.next_back() {
+            let value = self.value.next_back().unwrap();
+            Some((key, value))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a> ExactSizeIterator for IterMut<'a, K, V> {
+    fn len(&self) -> usize {
+        self.key.len()
+    }
+}
+
+impl<'a, K: 'a, V: 'a> FusedIterator for IterMut<'a, K, V> {}
+
+impl<'a, K: fmt::Debug, V: fmt::Debug> fmt::Debug for IterMut<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let iter = Iter {
+            key: self.key.clone(),
+            value: self.value.as_slice().iter(),
+        };
+        f.debug_list().entries(iter).finish()
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, K, V> IntoIterator for &'a mut LinearMap<K, V>
     where 
         K: PartialEq + 'a,
@@ -622,11 +2326,14 @@ impl<'a, K, V> IntoIterator for &'a mut LinearMap<K, V>
 /// An iterator over the entries of a LinearMap.
 ///
 /// This struct is created by the `into_iter` method on [`LinearMap`](struct.LinearMap.html). See its documentation for more.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
 pub struct IntoIter<K, V> {
     key: vec::IntoIter<K>,
     value: vec::IntoIter<V>,
 }
 
+#[cfg(feature = "alloc")]
 impl<K, V> Iterator for IntoIter<K, V> {
     type Item = (K, V);
 
@@ -638,8 +2345,46 @@ impl<K, V> Iterator for IntoIter<K, V> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.key.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<(K, V)> {
+        if let Some(key) = self.key.next_back() {
+            let value = self.value.next_back().unwrap();
+            Some((key, value))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+    fn len(&self) -> usize {
+        self.key.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+#[cfg(feature = "alloc")]
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for IntoIter<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let iter = Iter {
+            key: self.key.as_slice().iter(),
+            value: self.value.as_slice().iter(),
+        };
+        f.debug_list().entries(iter).finish()
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<K: PartialEq, V> IntoIterator for LinearMap<K, V> {
     type Item = (K, V);
     type IntoIter = IntoIter<K, V>;
@@ -650,4 +2395,300 @@ impl<K: PartialEq, V> IntoIterator for LinearMap<K, V> {
             value: self.values.into_iter(),
         }
     }
-}
\ No newline at end of file
+}
+
+/// A draining iterator over the entries of a LinearMap.
+///
+/// This struct is created by the `drain` method on [`LinearMap`](struct.LinearMap.html). See its documentation for more.
+#[cfg(feature = "alloc")]
+pub struct Drain<'a, K: 'a, V: 'a> {
+    key: vec::Drain<'a, K>,
+    value: vec::Drain<'a, V>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> Iterator for Drain<'a, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        if let Some(key) = self.key.next() {
+            let value = self.value.next().unwrap();
+            Some((key, value))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.key.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> DoubleEndedIterator for Drain<'a, K, V> {
+    fn next_back(&mut self) -> Option<(K, V)> {
+        if let Some(key) = self.key.next_back() {
+            let value = self.value.next_back().unwrap();
+            Some((key, value))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> ExactSizeIterator for Drain<'a, K, V> {
+    fn len(&self) -> usize {
+        self.key.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> FusedIterator for Drain<'a, K, V> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, K: fmt::Debug, V: fmt::Debug> fmt::Debug for Drain<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let iter = Iter {
+            key: self.key.as_slice().iter(),
+            value: self.value.as_slice().iter(),
+        };
+        f.debug_list().entries(iter).finish()
+    }
+}
+
+/// An iterator that removes the entries of a LinearMap for which a predicate returns
+/// `true`.
+///
+/// This struct is created by the `extract_if` method on [`LinearMap`](struct.LinearMap.html). See its documentation for more.
+#[cfg(feature = "alloc")]
+pub struct ExtractIf<'a, K: 'a, V: 'a, F>
+    where K: PartialEq
+{
+    // While the iterator is alive, the lengths of the vectors are 0. The entries `[0, index -
+    // deleted)` are retained, `[index - deleted, index)` have been moved out and
+    // `[index, old_len)` have not been visited yet.
+    map: &'a mut LinearMap<K, V>,
+    index: usize,
+    deleted: usize,
+    old_len: usize,
+    pred: F,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, F> Iterator for ExtractIf<'a, K, V, F>
+    where
+        K: PartialEq,
+        F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        unsafe {
+            while self.index < self.old_len {
+                let key = self.map.keys.as_mut_ptr().add(self.index);
+                let value = self.map.values.as_mut_ptr().add(self.index);
+                // `index` is advanced after the call, so an entry for which the predicate
+                // panics is retained by `drop`.
+                let extract = (self.pred)(&*key, &mut *value);
+                self.index += 1;
+                if extract {
+                    self.deleted += 1;
+                    return Some((ptr::read(key), ptr::read(value)));
+                } else if self.deleted > 0 {
+                    ptr::copy_nonoverlapping(key, key.sub(self.deleted), 1);
+                    ptr::copy_nonoverlapping(value, value.sub(self.deleted), 1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.index))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, F> Drop for ExtractIf<'a, K, V, F>
+    where K: PartialEq
+{
+    fn drop(&mut self) {
+        unsafe {
+            if self.deleted > 0 {
+                let key = self.map.keys.as_mut_ptr().add(self.index);
+                let value = self.map.values.as_mut_ptr().add(self.index);
+                ptr::copy(key, key.sub(self.deleted), self.old_len - self.index);
+                ptr::copy(value, value.sub(self.deleted), self.old_len - self.index);
+            }
+            self.map.keys.set_len(self.old_len - self.deleted);
+            self.map.values.set_len(self.old_len - self.deleted);
+        }
+    }
+}
+
+/// A consuming iterator over the keys of a LinearMap.
+///
+/// This struct is created by the `into_keys` method on [`LinearMap`](struct.LinearMap.html). See its documentation for more.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct IntoKeys<K> {
+    iter: vec::IntoIter<K>,
+}
+
+#[cfg(feature = "alloc")]
+impl<K> Iterator for IntoKeys<K> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K> DoubleEndedIterator for IntoKeys<K> {
+    fn next_back(&mut self) -> Option<K> {
+        self.iter.next_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K> ExactSizeIterator for IntoKeys<K> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K> FusedIterator for IntoKeys<K> {}
+
+#[cfg(feature = "alloc")]
+impl<K: fmt::Debug> fmt::Debug for IntoKeys<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter.as_slice()).finish()
+    }
+}
+
+/// A consuming iterator over the values of a LinearMap.
+///
+/// This struct is created by the `into_values` method on [`LinearMap`](struct.LinearMap.html). See its documentation for more.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct IntoValues<V> {
+    iter: vec::IntoIter<V>,
+}
+
+#[cfg(feature = "alloc")]
+impl<V> Iterator for IntoValues<V> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<V> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<V> DoubleEndedIterator for IntoValues<V> {
+    fn next_back(&mut self) -> Option<V> {
+        self.iter.next_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<V> ExactSizeIterator for IntoValues<V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<V> FusedIterator for IntoValues<V> {}
+
+#[cfg(feature = "alloc")]
+impl<V: fmt::Debug> fmt::Debug for IntoValues<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter.as_slice()).finish()
+    }
+}
+
+/// The error returned by [`LinearMap::from_parts`](struct.LinearMap.html#method.from_parts)
+/// when the keys and values do not form a valid map. It owns the vectors that were passed
+/// in, which can be taken back with [`into_parts`](#method.into_parts).
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq)]
+pub struct FromPartsError<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    kind: FromPartsErrorKind,
+}
+
+/// The reason why [`LinearMap::from_parts`](struct.LinearMap.html#method.from_parts)
+/// failed.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FromPartsErrorKind {
+    /// The vectors of keys and values have different lengths.
+    LengthMismatch,
+    /// The key at `index` is equal to a key before it.
+    DuplicateKey {
+        /// The position of the second occurrence of the key.
+        index: usize,
+    },
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> FromPartsError<K, V> {
+    /// Returns the reason of the error.
+    pub fn kind(&self) -> FromPartsErrorKind {
+        self.kind
+    }
+
+    /// Returns the keys that were passed to `from_parts`.
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Returns the values that were passed to `from_parts`.
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Returns the vectors of keys and values that were passed to `from_parts`.
+    pub fn into_parts(self) -> (Vec<K>, Vec<V>) {
+        (self.keys, self.values)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> fmt::Debug for FromPartsError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FromPartsError")
+            .field("kind", &self.kind)
+            .field("keys", &self.keys.len())
+            .field("values", &self.values.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> fmt::Display for FromPartsError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            FromPartsErrorKind::LengthMismatch => {
+                write!(f, "{} keys and {} values have different lengths", self.keys.len(), self.values.len())
+            }
+            FromPartsErrorKind::DuplicateKey { index } => write!(f, "duplicate key at index {}", index),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> std::error::Error for FromPartsError<K, V> {}
\ No newline at end of file
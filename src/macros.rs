@@ -0,0 +1,99 @@
+/// Creates a [`LinearMap`](struct.LinearMap.html) from `key => value` pairs.
+///
+/// The map is allocated with room for all pairs, which are inserted in the given order. If a
+/// key occurs more than once, the last value is kept at the position of the first
+/// occurrence, like with repeated calls to [`insert`](struct.LinearMap.html#method.insert).
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate linear_map;
+///
+/// # fn main() {
+/// let map = linear_map! {
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert_eq!(map["a"], 1);
+/// assert!(map.keys().eq(&["a", "b"]));
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! linear_map {
+    (@unit $key:expr) => (());
+    ($($key:expr => $value:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::LinearMap::with_capacity(
+            <[()]>::len(&[$($crate::linear_map!(@unit $key)),*])
+        );
+        $(
+            let _ = map.insert($key, $value);
+        )*
+        map
+    }};
+}
+
+/// Creates a [`StaticLinearMap`](struct.StaticLinearMap.html) from `key => value` pairs.
+///
+/// The pairs are stored in a `&'static` slice, so the macro can be used to initialize a
+/// `static` or `const`. The keys and values must therefore be constant expressions.
+///
+/// When all keys are literals, the macro matches them against each other in a `match` that
+/// denies unreachable patterns, so a duplicate key fails to compile. Other keys, like paths
+/// to constants, are accepted without this check, and a duplicate of them is found by the
+/// lookup methods only in its first entry.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate linear_map;
+/// use linear_map::StaticLinearMap;
+///
+/// static UNITS: StaticLinearMap<&str, u64> = static_linear_map! {
+///     "KiB" => 1 << 10,
+///     "MiB" => 1 << 20,
+///     "GiB" => 1 << 30,
+/// };
+///
+/// # fn main() {
+/// assert_eq!(UNITS["MiB"], 1048576);
+/// # }
+/// ```
+///
+/// Duplicate literal keys are rejected:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate linear_map;
+/// use linear_map::StaticLinearMap;
+///
+/// static UNITS: StaticLinearMap<&str, u64> = static_linear_map! {
+///     "KiB" => 1 << 10,
+///     "KiB" => 1 << 20,
+/// };
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! static_linear_map {
+    ($($key:literal => $value:expr),* $(,)?) => {
+        match $crate::StaticLinearMap::new(&[$(($key, $value)),*]) {
+            map => {
+                $crate::static_map::__check_keys(&map, &|key| {
+                    #[deny(unreachable_patterns)]
+                    match *key {
+                        $($key => {})*
+                        #[allow(unreachable_patterns)]
+                        _ => {}
+                    }
+                });
+                map
+            }
+        }
+    };
+    ($($key:expr => $value:expr),* $(,)?) => {
+        $crate::StaticLinearMap::new(&[$(($key, $value)),*])
+    };
+}
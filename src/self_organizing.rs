@@ -0,0 +1,477 @@
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FromIterator;
+use core::mem;
+
+use {IntoIter, Iter, IterMut, Keys, LinearMap, Values, ValuesMut};
+
+/// How a [`SelfOrganizingLinearMap`](../struct.SelfOrganizingLinearMap.html) reorders its
+/// entries when a key is found.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// The entry is moved to index 0, shifting the entries before it back by one. Adapts
+    /// quickly to changing access patterns, but a single access to a rare key pushes all
+    /// frequent keys back.
+    #[default]
+    MoveToFront,
+    /// The entry is swapped with the one before it. Frequent keys move to the front more
+    /// slowly, but the order is more stable under occasional accesses to rare keys.
+    Transpose,
+}
+
+/// SelfOrganizingLinearMap is a [`LinearMap`](struct.LinearMap.html) that moves entries
+/// towards the front when they are looked up, so that frequently accessed keys are found
+/// after fewer comparisons. This helps with skewed access patterns like dispatch tables
+/// where a few keys make up most of the lookups.
+///
+/// The reordering is applied by `get`, `get_mut`, `get_key_value` and `insert` of an
+/// existing key, which therefore take `&mut self`. The `peek` methods look up a key without
+/// changing the order. `remove` preserves the order of the remaining entries, since
+/// swapping the last entry into the gap would move a rarely used key to the front.
+///
+/// # Examples
+///
+/// ```
+/// extern crate linear_map;
+/// use linear_map::SelfOrganizingLinearMap;
+///
+/// let mut map = SelfOrganizingLinearMap::new();
+/// map.insert("add", 1);
+/// map.insert("sub", 2);
+/// map.insert("mul", 3);
+///
+/// assert_eq!(map.get("mul"), Some(&3));
+/// assert!(map.keys().eq(&["mul", "add", "sub"]));
+///
+/// assert_eq!(map.peek("sub"), Some(&2));
+/// assert!(map.keys().eq(&["mul", "add", "sub"]));
+/// ```
+#[derive(Clone)]
+pub struct SelfOrganizingLinearMap<K, V>
+    where K: PartialEq
+{
+    map: LinearMap<K, V>,
+    policy: Policy,
+}
+
+impl<K, V> SelfOrganizingLinearMap<K, V>
+    where K: PartialEq
+{
+    /// Creates an empty map with the [`MoveToFront`](self_organizing/enum.Policy.html)
+    /// policy. This method does not allocate.
+    pub fn new() -> Self {
+        SelfOrganizingLinearMap::with_policy(Policy::default())
+    }
+
+    /// Creates an empty map with the given policy. This method does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SelfOrganizingLinearMap;
+    /// use linear_map::self_organizing::Policy;
+    ///
+    /// let mut map = SelfOrganizingLinearMap::with_policy(Policy::Transpose);
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    ///
+    /// map.get(&3);
+    /// assert!(map.keys().eq(&[1, 3, 2]));
+    /// ```
+    pub fn with_policy(policy: Policy) -> Self {
+        SelfOrganizingLinearMap { map: LinearMap::new(), policy }
+    }
+
+    /// Creates an empty map with the given initial capacity and policy.
+    pub fn with_capacity_and_policy(capacity: usize, policy: Policy) -> Self {
+        SelfOrganizingLinearMap { map: LinearMap::with_capacity(capacity), policy }
+    }
+
+    /// Creates a map from the entries of `map` that reorders them with the given policy.
+    pub fn from_linear_map(map: LinearMap<K, V>, policy: Policy) -> Self {
+        SelfOrganizingLinearMap { map, policy }
+    }
+
+    /// Returns the policy that is applied on lookups.
+    pub fn policy(&self) -> Policy {
+        self.policy
+    }
+
+    /// Changes the policy that is applied on lookups. The current order is kept.
+    pub fn set_policy(&mut self, policy: Policy) {
+        self.policy = policy;
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Clears the map, removing all elements. Keeps the allocated memory for reuse.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns a reference to the value corresponding to the key and moves the entry
+    /// towards the front according to the policy.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.find_and_reorder(key) {
+            Some(i) => Some(&self.map.values[i]),
+            None => None,
+        }
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key and moves the
+    /// entry towards the front according to the policy.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.find_and_reorder(key) {
+            Some(i) => Some(&mut self.map.values[i]),
+            None => None,
+        }
+    }
+
+    /// Returns the key-value pair corresponding to the key and moves the entry towards the
+    /// front according to the policy.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_key_value<Q>(&mut self, key: &Q) -> Option<(&K, &V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.find_and_reorder(key) {
+            Some(i) => Some((&self.map.keys[i], &self.map.values[i])),
+            None => None,
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key without changing the
+    /// order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key without changing
+    /// the order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn peek_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.get_mut(key)
+    }
+
+    /// Returns the key-value pair corresponding to the key without changing the order of
+    /// the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn peek_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.get_key_value(key)
+    }
+
+    /// Returns true if the map contains a value for the specified key. The order of the
+    /// entries is not changed.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// A new key is appended at the end. If the map already had this key present, the value
+    /// is updated, the entry is moved towards the front according to the policy, and the
+    /// old value is returned.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SelfOrganizingLinearMap;
+    ///
+    /// let mut map = SelfOrganizingLinearMap::new();
+    /// assert_eq!(map.insert(1, "a"), None);
+    /// assert_eq!(map.insert(2, "b"), None);
+    /// assert_eq!(map.insert(2, "c"), Some("b"));
+    /// assert!(map.keys().eq(&[2, 1]));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.find_and_reorder(&key) {
+            Some(i) => Some(mem::replace(&mut self.map.values[i], value)),
+            None => {
+                self.map.keys.push(key);
+                self.map.values.push(value);
+                None
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously
+    /// in the map. The order of the remaining entries is preserved.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.shift_remove(key)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key was
+    /// previously in the map. The order of the remaining entries is preserved.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.shift_remove_entry(key)
+    }
+
+    /// Returns an iterator over the keys, starting with the most recently or most
+    /// frequently accessed ones.
+    pub fn keys(&self) -> Keys<'_, K> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator over the values in the order of their keys.
+    pub fn values(&self) -> Values<'_, V> {
+        self.map.values()
+    }
+
+    /// Returns an iterator over mutable references to the values in the order of their keys.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        self.map.values_mut()
+    }
+
+    /// Returns an iterator over the key-value pairs, starting with the most recently or most
+    /// frequently accessed ones.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// Returns an iterator over the key-value pairs with mutable references to the values,
+    /// starting with the most recently or most frequently accessed ones.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Returns the underlying `LinearMap`.
+    pub fn as_linear_map(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+
+    /// Converts the map into a `LinearMap` without copying the entries. The order of the
+    /// entries is kept.
+    pub fn into_linear_map(self) -> LinearMap<K, V> {
+        self.map
+    }
+
+    /// Finds the key and moves its entry according to the policy. Returns the new index.
+    fn find_and_reorder<Q>(&mut self, key: &Q) -> Option<usize>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        let i = self.map.find(key)?;
+        let keys = &mut self.map.keys;
+        let values = &mut self.map.values;
+        match self.policy {
+            Policy::MoveToFront => {
+                keys[..=i].rotate_right(1);
+                values[..=i].rotate_right(1);
+                Some(0)
+            }
+            Policy::Transpose if i > 0 => {
+                keys.swap(i - 1, i);
+                values.swap(i - 1, i);
+                Some(i - 1)
+            }
+            Policy::Transpose => Some(0),
+        }
+    }
+}
+
+impl<K, V> Default for SelfOrganizingLinearMap<K, V>
+    where K: PartialEq
+{
+    /// Creates an empty `SelfOrganizingLinearMap`.
+    fn default() -> Self {
+        SelfOrganizingLinearMap::new()
+    }
+}
+
+impl<K, V> fmt::Debug for SelfOrganizingLinearMap<K, V>
+    where
+        K: PartialEq + fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+/// The order of the entries and the policies of the maps are ignored.
+impl<K, V> PartialEq for SelfOrganizingLinearMap<K, V>
+    where
+        K: PartialEq,
+        V: PartialEq,
+{
+    fn eq(&self, other: &SelfOrganizingLinearMap<K, V>) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K, V> Eq for SelfOrganizingLinearMap<K, V>
+    where
+        K: Eq,
+        V: Eq,
+{
+}
+
+impl<K, V> From<LinearMap<K, V>> for SelfOrganizingLinearMap<K, V>
+    where K: PartialEq
+{
+    fn from(map: LinearMap<K, V>) -> Self {
+        SelfOrganizingLinearMap::from_linear_map(map, Policy::default())
+    }
+}
+
+impl<K, V> From<SelfOrganizingLinearMap<K, V>> for LinearMap<K, V>
+    where K: PartialEq
+{
+    fn from(map: SelfOrganizingLinearMap<K, V>) -> Self {
+        map.map
+    }
+}
+
+/// Collects the entries without reordering them. Later values of duplicate keys replace
+/// earlier ones in place.
+impl<K, V> FromIterator<(K, V)> for SelfOrganizingLinearMap<K, V>
+    where K: PartialEq
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        SelfOrganizingLinearMap::from(iter.into_iter().collect::<LinearMap<K, V>>())
+    }
+}
+
+/// Inserts the entries without reordering existing ones. Values of existing keys are
+/// replaced in place.
+impl<K, V> Extend<(K, V)> for SelfOrganizingLinearMap<K, V>
+    where K: PartialEq
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.map.extend(iter);
+    }
+}
+
+impl<'a, K, V> Extend<(&'a K, &'a V)> for SelfOrganizingLinearMap<K, V>
+    where
+        K: PartialEq + Copy + 'a,
+        V: Copy + 'a,
+{
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.map.extend(iter);
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SelfOrganizingLinearMap<K, V>
+    where K: PartialEq
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut SelfOrganizingLinearMap<K, V>
+    where K: PartialEq
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> IntoIterator for SelfOrganizingLinearMap<K, V>
+    where K: PartialEq
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
@@ -0,0 +1,31 @@
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use {LinearMap, LinearSet};
+
+/// Serializes the map as a map in [storage order](struct.LinearMap.html#ordering).
+impl<K, V> Serialize for LinearMap<K, V>
+    where
+        K: PartialEq + Serialize,
+        V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self.iter() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Serializes the set as a sequence in [storage order](struct.LinearMap.html#ordering).
+impl<T> Serialize for LinearSet<T>
+    where T: PartialEq + Serialize
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for value in self.iter() {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
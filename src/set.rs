@@ -0,0 +1,797 @@
+use core::borrow::Borrow;
+use core::cmp;
+use core::fmt;
+use core::iter::{Chain, FromIterator, FusedIterator};
+use core::slice;
+
+use {IntoIter as MapIntoIter, LinearMap};
+
+/// LinearSet is a set that is implemented on top of [`LinearMap`](struct.LinearMap.html)
+/// with `()` as the value type. Since `()` occupies no memory, only the elements are
+/// allocated. The same linear search is used for every lookup, so the performance
+/// considerations of LinearMap apply to LinearSet as well.
+///
+/// The elements are stored in the order in which they were first inserted, see the
+/// [ordering](struct.LinearMap.html#ordering) of LinearMap.
+///
+/// # Examples
+///
+/// ```
+/// use linear_map::LinearSet;
+///
+/// let mut books = LinearSet::new();
+///
+/// // Add some books.
+/// books.insert("A Dance With Dragons");
+/// books.insert("To Kill a Mockingbird");
+/// books.insert("The Odyssey");
+/// books.insert("The Great Gatsby");
+///
+/// // Check for a specific one.
+/// if !books.contains("The Winds of Winter") {
+///     println!("We have {} books, but The Winds of Winter ain't one.",
+///              books.len());
+/// }
+///
+/// // Remove a book.
+/// books.remove("The Odyssey");
+///
+/// // Iterate over everything.
+/// for book in &books {
+///     println!("{}", book);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct LinearSet<T>
+    where T: PartialEq
+{
+    map: LinearMap<T, ()>,
+}
+
+impl<T> LinearSet<T>
+    where T: PartialEq
+{
+    /// Creates an empty `LinearSet`.
+    ///
+    /// The set is initially created with a capacity of 0, so it will not allocate until it
+    /// is first inserted into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let set: LinearSet<i32> = LinearSet::new();
+    /// ```
+    pub const fn new() -> Self {
+        LinearSet { map: LinearMap::new() }
+    }
+
+    /// Creates an empty `LinearSet` with the specified capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let set: LinearSet<i32> = LinearSet::with_capacity(10);
+    /// assert!(set.capacity() >= 10);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        LinearSet { map: LinearMap::with_capacity(capacity) }
+    }
+
+    /// Returns the number of elements the set can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Clears the set, removing all elements. Keeps the allocated memory for reuse.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the number of elements in the set.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted. If the set already contained the
+    /// value, the stored value is not updated.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let mut set = LinearSet::new();
+    /// assert!(set.insert(2));
+    /// assert!(!set.insert(2));
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// Returns `true` if the set contains the value.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let set: LinearSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// assert!(set.contains(&1));
+    /// assert!(!set.contains(&4));
+    /// ```
+    pub fn contains<Q>(&self, value: &Q) -> bool
+        where
+            T: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Returns a reference to the value in the set that is equal to the given value.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let set: LinearSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// assert_eq!(set.get(&2), Some(&2));
+    /// assert_eq!(set.get(&4), None);
+    /// ```
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+        where
+            T: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.get_key_value(value).map(|(value, _)| value)
+    }
+
+    /// Removes a value from the set. Returns whether the value was present in the set.
+    ///
+    /// The last element of the set takes the place of the removed one, like
+    /// [`LinearMap::swap_remove`](struct.LinearMap.html#method.swap_remove).
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let mut set = LinearSet::new();
+    /// set.insert(2);
+    /// assert!(set.remove(&2));
+    /// assert!(!set.remove(&2));
+    /// ```
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+        where
+            T: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.swap_remove(value).is_some()
+    }
+
+    /// Removes a value from the set while preserving the order of the remaining elements.
+    /// Returns whether the value was present in the set.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let mut set: LinearSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// assert!(set.shift_remove(&1));
+    /// assert!(set.iter().eq(&[2, 3]));
+    /// ```
+    pub fn shift_remove<Q>(&mut self, value: &Q) -> bool
+        where
+            T: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.shift_remove(value).is_some()
+    }
+
+    /// Removes and returns the value in the set that is equal to the given one.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let mut set: LinearSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// assert_eq!(set.take(&2), Some(2));
+    /// assert_eq!(set.take(&2), None);
+    /// ```
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+        where
+            T: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.map.swap_remove_entry(value).map(|(value, _)| value)
+    }
+
+    /// Gets an iterator over the elements of the set in
+    /// [storage order](struct.LinearMap.html#ordering).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let mut set = LinearSet::new();
+    /// set.insert("a");
+    /// set.insert("b");
+    ///
+    /// for x in set.iter() {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { iter: self.map.keys.iter() }
+    }
+
+    /// Visits the values representing the union, i.e., all the values in `self` or `other`,
+    /// without duplicates.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n * m) where n and m are the number of elements in the sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let a: LinearSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: LinearSet<_> = vec![4, 2, 3, 4].into_iter().collect();
+    ///
+    /// let union: Vec<_> = a.union(&b).cloned().collect();
+    /// assert_eq!(union, [1, 2, 3, 4]);
+    /// ```
+    pub fn union<'a>(&'a self, other: &'a LinearSet<T>) -> Union<'a, T> {
+        Union { iter: self.iter().chain(other.difference(self)) }
+    }
+
+    /// Visits the values representing the intersection, i.e., the values that are both
+    /// in `self` and `other`.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n * m) where n and m are the number of elements in the sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let a: LinearSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: LinearSet<_> = vec![4, 2, 3, 4].into_iter().collect();
+    ///
+    /// let intersection: Vec<_> = a.intersection(&b).cloned().collect();
+    /// assert_eq!(intersection, [2, 3]);
+    /// ```
+    pub fn intersection<'a>(&'a self, other: &'a LinearSet<T>) -> Intersection<'a, T> {
+        Intersection { iter: self.iter(), other }
+    }
+
+    /// Visits the values representing the difference, i.e., the values that are in `self`
+    /// but not in `other`.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n * m) where n and m are the number of elements in the sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let a: LinearSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: LinearSet<_> = vec![4, 2, 3, 4].into_iter().collect();
+    ///
+    /// let diff: Vec<_> = a.difference(&b).cloned().collect();
+    /// assert_eq!(diff, [1]);
+    ///
+    /// let diff: Vec<_> = b.difference(&a).cloned().collect();
+    /// assert_eq!(diff, [4]);
+    /// ```
+    pub fn difference<'a>(&'a self, other: &'a LinearSet<T>) -> Difference<'a, T> {
+        Difference { iter: self.iter(), other }
+    }
+
+    /// Visits the values representing the symmetric difference, i.e., the values that are
+    /// in `self` or in `other` but not in both.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n * m) where n and m are the number of elements in the sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let a: LinearSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: LinearSet<_> = vec![4, 2, 3, 4].into_iter().collect();
+    ///
+    /// let diff: Vec<_> = a.symmetric_difference(&b).cloned().collect();
+    /// assert_eq!(diff, [1, 4]);
+    /// ```
+    pub fn symmetric_difference<'a>(&'a self, other: &'a LinearSet<T>) -> SymmetricDifference<'a, T> {
+        SymmetricDifference { iter: self.difference(other).chain(other.difference(self)) }
+    }
+
+    /// Returns `true` if `self` has no elements in common with `other`.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n * m) where n and m are the number of elements in the sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let a: LinearSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let mut b = LinearSet::new();
+    ///
+    /// assert!(a.is_disjoint(&b));
+    /// b.insert(4);
+    /// assert!(a.is_disjoint(&b));
+    /// b.insert(1);
+    /// assert!(!a.is_disjoint(&b));
+    /// ```
+    pub fn is_disjoint(&self, other: &LinearSet<T>) -> bool {
+        self.iter().all(|value| !other.contains(value))
+    }
+
+    /// Returns `true` if all elements of `self` are contained in `other`.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n * m) where n and m are the number of elements in the sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let sup: LinearSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let mut set = LinearSet::new();
+    ///
+    /// assert!(set.is_subset(&sup));
+    /// set.insert(2);
+    /// assert!(set.is_subset(&sup));
+    /// set.insert(4);
+    /// assert!(!set.is_subset(&sup));
+    /// ```
+    pub fn is_subset(&self, other: &LinearSet<T>) -> bool {
+        self.len() <= other.len() && self.iter().all(|value| other.contains(value))
+    }
+
+    /// Returns `true` if all elements of `other` are contained in `self`.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n * m) where n and m are the number of elements in the sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::LinearSet;
+    ///
+    /// let sub: LinearSet<_> = vec![1, 2].into_iter().collect();
+    /// let mut set = LinearSet::new();
+    ///
+    /// assert!(!set.is_superset(&sub));
+    /// set.insert(0);
+    /// set.insert(1);
+    /// assert!(!set.is_superset(&sub));
+    /// set.insert(2);
+    /// assert!(set.is_superset(&sub));
+    /// ```
+    pub fn is_superset(&self, other: &LinearSet<T>) -> bool {
+        other.is_subset(self)
+    }
+}
+
+impl<T> Default for LinearSet<T>
+    where T: PartialEq
+{
+    /// Creates an empty `LinearSet`.
+    fn default() -> Self {
+        LinearSet::new()
+    }
+}
+
+impl<T> fmt::Debug for LinearSet<T>
+    where T: PartialEq + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Two sets are equal when they contain the same elements, regardless of the order in
+/// which the elements are stored.
+impl<T> PartialEq for LinearSet<T>
+    where T: PartialEq
+{
+    fn eq(&self, other: &LinearSet<T>) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<T> Eq for LinearSet<T>
+    where T: Eq
+{
+}
+
+impl<T> FromIterator<T> for LinearSet<T>
+    where T: PartialEq
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> LinearSet<T> {
+        let mut set = LinearSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> Extend<T> for LinearSet<T>
+    where T: PartialEq
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|value| (value, ())));
+    }
+}
+
+impl<'a, T> Extend<&'a T> for LinearSet<T>
+    where T: PartialEq + Copy + 'a
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+/// An iterator over the elements of a LinearSet.
+///
+/// This struct is created by the `iter` method on [`LinearSet`](struct.LinearSet.html). See its documentation for more.
+pub struct Iter<'a, T: 'a> {
+    iter: slice::Iter<'a, T>,
+}
+
+impl<'a, T: 'a> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T: 'a> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.iter.next_back()
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, T: 'a> FusedIterator for Iter<'a, T> {}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter { iter: self.iter.clone() }
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Iter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter.as_slice()).finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinearSet<T>
+    where T: PartialEq + 'a
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// An owning iterator over the elements of a LinearSet.
+///
+/// This struct is created by the `into_iter` method on [`LinearSet`](struct.LinearSet.html). See its documentation for more.
+#[derive(Clone)]
+pub struct IntoIter<T> {
+    iter: MapIntoIter<T, ()>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(|(value, _)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back().map(|(value, _)| value)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter.key.as_slice()).finish()
+    }
+}
+
+impl<T> IntoIterator for LinearSet<T>
+    where T: PartialEq
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { iter: self.map.into_iter() }
+    }
+}
+
+/// A lazy iterator producing elements in the union of two LinearSets.
+///
+/// This struct is created by the `union` method on [`LinearSet`](struct.LinearSet.html). See its documentation for more.
+pub struct Union<'a, T: 'a>
+    where T: PartialEq
+{
+    iter: Chain<Iter<'a, T>, Difference<'a, T>>,
+}
+
+impl<'a, T> Iterator for Union<'a, T>
+    where T: PartialEq
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> FusedIterator for Union<'a, T>
+    where T: PartialEq
+{
+}
+
+impl<'a, T> Clone for Union<'a, T>
+    where T: PartialEq
+{
+    fn clone(&self) -> Self {
+        Union { iter: self.iter.clone() }
+    }
+}
+
+impl<'a, T> fmt::Debug for Union<'a, T>
+    where T: PartialEq + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// A lazy iterator producing elements in the intersection of two LinearSets.
+///
+/// This struct is created by the `intersection` method on [`LinearSet`](struct.LinearSet.html). See its documentation for more.
+pub struct Intersection<'a, T: 'a>
+    where T: PartialEq
+{
+    iter: Iter<'a, T>,
+    other: &'a LinearSet<T>,
+}
+
+impl<'a, T> Iterator for Intersection<'a, T>
+    where T: PartialEq
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.by_ref().find(|value| other.contains(value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(cmp::min(self.iter.len(), self.other.len())))
+    }
+}
+
+impl<'a, T> FusedIterator for Intersection<'a, T>
+    where T: PartialEq
+{
+}
+
+impl<'a, T> Clone for Intersection<'a, T>
+    where T: PartialEq
+{
+    fn clone(&self) -> Self {
+        Intersection { iter: self.iter.clone(), other: self.other }
+    }
+}
+
+impl<'a, T> fmt::Debug for Intersection<'a, T>
+    where T: PartialEq + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// A lazy iterator producing elements in the difference of two LinearSets.
+///
+/// This struct is created by the `difference` method on [`LinearSet`](struct.LinearSet.html). See its documentation for more.
+pub struct Difference<'a, T: 'a>
+    where T: PartialEq
+{
+    iter: Iter<'a, T>,
+    other: &'a LinearSet<T>,
+}
+
+impl<'a, T> Iterator for Difference<'a, T>
+    where T: PartialEq
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.by_ref().find(|value| !other.contains(value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The elements of both sets are unique, so at most `other.len()` of them are skipped.
+        let len = self.iter.len();
+        (len.saturating_sub(self.other.len()), Some(len))
+    }
+}
+
+impl<'a, T> FusedIterator for Difference<'a, T>
+    where T: PartialEq
+{
+}
+
+impl<'a, T> Clone for Difference<'a, T>
+    where T: PartialEq
+{
+    fn clone(&self) -> Self {
+        Difference { iter: self.iter.clone(), other: self.other }
+    }
+}
+
+impl<'a, T> fmt::Debug for Difference<'a, T>
+    where T: PartialEq + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// A lazy iterator producing elements in the symmetric difference of two LinearSets.
+///
+/// This struct is created by the `symmetric_difference` method on [`LinearSet`](struct.LinearSet.html). See its documentation for more.
+pub struct SymmetricDifference<'a, T: 'a>
+    where T: PartialEq
+{
+    iter: Chain<Difference<'a, T>, Difference<'a, T>>,
+}
+
+impl<'a, T> Iterator for SymmetricDifference<'a, T>
+    where T: PartialEq
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> FusedIterator for SymmetricDifference<'a, T>
+    where T: PartialEq
+{
+}
+
+impl<'a, T> Clone for SymmetricDifference<'a, T>
+    where T: PartialEq
+{
+    fn clone(&self) -> Self {
+        SymmetricDifference { iter: self.iter.clone() }
+    }
+}
+
+impl<'a, T> fmt::Debug for SymmetricDifference<'a, T>
+    where T: PartialEq + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
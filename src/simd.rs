@@ -0,0 +1,148 @@
+//! Vectorised search over keys that compare equal exactly when their bytes are equal.
+//!
+//! The keys are viewed as bytes and compared 16 or 32 bytes at a time. The comparison yields
+//! one bit per byte, and a key matches when all bits of its lane are set. On x86_64 SSE2 is
+//! always available, AVX2 is used when it is detected at runtime (with `std`) or enabled at
+//! compile time. Other targets, 8 byte keys without AVX2, and the remainder that does not
+//! fill a vector are searched with a scalar loop.
+
+/// Returns the index of the first `W` byte lane of `haystack` that equals `needle`.
+///
+/// `haystack.len()` must be a multiple of `W`, which must be 1, 2, 4 or 8.
+#[inline]
+pub(crate) fn find<const W: usize>(haystack: &[u8], needle: &[u8; W]) -> Option<usize> {
+    debug_assert!(W.is_power_of_two() && W <= 8);
+    debug_assert_eq!(haystack.len() % W, 0);
+
+    let mut pattern = [0; 32];
+    for lane in pattern.chunks_exact_mut(W) {
+        lane.copy_from_slice(needle);
+    }
+
+    let (found, searched) = kernel::<W>(haystack, &pattern);
+    if let Some(offset) = found {
+        return Some(offset / W);
+    }
+    haystack[searched..]
+        .chunks_exact(W)
+        .position(|lane| lane == needle)
+        .map(|i| searched / W + i)
+}
+
+/// The minimum number of bytes for which AVX2 is used. Calling the AVX2 kernel when it is
+/// only detected at runtime has a fixed cost that small searches do not amortize.
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+const AVX2_MIN_LEN: usize = 32;
+#[cfg(all(target_arch = "x86_64", not(target_feature = "avx2")))]
+const AVX2_MIN_LEN: usize = 512;
+
+/// Searches the whole vectors at the start of `haystack`. Returns the byte offset of the
+/// first match and the number of bytes that were searched.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn kernel<const W: usize>(haystack: &[u8], pattern: &[u8; 32]) -> (Option<usize>, usize) {
+    let mut searched = 0;
+    if haystack.len() >= AVX2_MIN_LEN && has_avx2() {
+        // SAFETY: The CPU supports AVX2.
+        let (found, end) = unsafe { x86_64::find_avx2::<W>(haystack, pattern) };
+        if found.is_some() {
+            return (found, end);
+        }
+        searched = end;
+    }
+    // With only two 8 byte lanes per vector, SSE2 is not faster than the scalar loop.
+    if W == 8 {
+        return (None, searched);
+    }
+    let (found, end) = x86_64::find_sse2::<W>(&haystack[searched..], pattern);
+    (found.map(|offset| searched + offset), searched + end)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+fn has_avx2() -> bool {
+    #[cfg(target_feature = "avx2")]
+    {
+        true
+    }
+    #[cfg(all(feature = "std", not(target_feature = "avx2")))]
+    {
+        ::std::is_x86_feature_detected!("avx2")
+    }
+    #[cfg(all(not(feature = "std"), not(target_feature = "avx2")))]
+    {
+        false
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+#[inline(always)]
+fn kernel<const W: usize>(_haystack: &[u8], _pattern: &[u8; 32]) -> (Option<usize>, usize) {
+    (None, 0)
+}
+
+/// Reduces a mask with one bit per matching byte to the bits of the lanes whose bytes all
+/// match. Only the lowest bit of each lane remains set.
+#[inline(always)]
+fn lane_mask<const W: usize>(mut mask: u32) -> u32 {
+    let mut shift = 1;
+    while shift < W {
+        mask &= mask >> shift;
+        shift *= 2;
+    }
+    match W {
+        1 => mask,
+        2 => mask & 0x5555_5555,
+        4 => mask & 0x1111_1111,
+        _ => mask & 0x0101_0101,
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use core::arch::x86_64::*;
+
+    use super::lane_mask;
+
+    #[inline(always)]
+    pub(super) fn find_sse2<const W: usize>(haystack: &[u8], pattern: &[u8; 32]) -> (Option<usize>, usize) {
+        let end = haystack.len() - haystack.len() % 16;
+        // SAFETY: SSE2 is part of the x86_64 baseline, and every load reads 16 bytes that
+        // are in bounds of `pattern` or `haystack[..end]`.
+        unsafe {
+            let needle = _mm_loadu_si128(pattern.as_ptr() as *const __m128i);
+            let mut offset = 0;
+            while offset < end {
+                let keys = _mm_loadu_si128(haystack.as_ptr().add(offset) as *const __m128i);
+                let mask = _mm_movemask_epi8(_mm_cmpeq_epi8(keys, needle)) as u32;
+                let mask = lane_mask::<W>(mask);
+                if mask != 0 {
+                    return (Some(offset + mask.trailing_zeros() as usize), end);
+                }
+                offset += 16;
+            }
+        }
+        (None, end)
+    }
+
+    /// # Safety
+    ///
+    /// The CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn find_avx2<const W: usize>(haystack: &[u8], pattern: &[u8; 32]) -> (Option<usize>, usize) {
+        let end = haystack.len() - haystack.len() % 32;
+        let needle = _mm256_loadu_si256(pattern.as_ptr() as *const __m256i);
+        let mut offset = 0;
+        while offset < end {
+            // SAFETY: The 32 bytes at `offset` are in bounds of `haystack[..end]`.
+            let keys = _mm256_loadu_si256(haystack.as_ptr().add(offset) as *const __m256i);
+            let mask = _mm256_movemask_epi8(_mm256_cmpeq_epi8(keys, needle)) as u32;
+            let mask = lane_mask::<W>(mask);
+            if mask != 0 {
+                return (Some(offset + mask.trailing_zeros() as usize), end);
+            }
+            offset += 32;
+        }
+        (None, end)
+    }
+}
@@ -0,0 +1,560 @@
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FromIterator;
+use core::mem;
+use core::ops::Index;
+
+use array::{self, ArrayLinearMap};
+use {IntoIter as MapIntoIter, Iter, IterMut, Keys, LinearMap, Values, ValuesMut};
+
+/// SmallLinearMap is a map that stores up to `N` entries inline like
+/// [`ArrayLinearMap`](struct.ArrayLinearMap.html) and moves them to the heap, into the
+/// layout of [`LinearMap`](struct.LinearMap.html), as soon as a new key does not fit
+/// anymore. Maps that usually stay small therefore do not allocate, while the few that grow
+/// larger keep working without a capacity limit.
+///
+/// Once spilled, the entries stay on the heap until [`shrink_to_inline`](#method.shrink_to_inline)
+/// is called. Moving the entries preserves their order, so the map provides the same
+/// [ordering](struct.LinearMap.html#ordering) guarantees as LinearMap.
+///
+/// # Examples
+///
+/// ```
+/// use linear_map::SmallLinearMap;
+///
+/// let mut map: SmallLinearMap<u32, &str, 2> = SmallLinearMap::new();
+/// map.insert(1, "a");
+/// map.insert(2, "b");
+/// assert!(!map.spilled());
+///
+/// map.insert(3, "c");
+/// assert!(map.spilled());
+///
+/// map.remove(&3);
+/// assert!(map.shrink_to_inline());
+/// assert!(!map.spilled());
+/// ```
+#[derive(Clone)]
+pub struct SmallLinearMap<K, V, const N: usize>
+    where K: PartialEq
+{
+    storage: Storage<K, V, N>,
+}
+
+#[derive(Clone)]
+enum Storage<K, V, const N: usize>
+    where K: PartialEq
+{
+    Inline(ArrayLinearMap<K, V, N>),
+    Heap(LinearMap<K, V>),
+}
+
+impl<K, V, const N: usize> SmallLinearMap<K, V, N>
+    where K: PartialEq
+{
+    /// Creates an empty `SmallLinearMap` which stores its entries inline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SmallLinearMap;
+    ///
+    /// let map: SmallLinearMap<usize, &str, 4> = SmallLinearMap::new();
+    /// assert!(!map.spilled());
+    /// ```
+    pub const fn new() -> Self {
+        SmallLinearMap { storage: Storage::Inline(ArrayLinearMap::new()) }
+    }
+
+    /// Creates an empty `SmallLinearMap` with the specified capacity. If `capacity` is
+    /// larger than `N`, the entries are stored on the heap right away.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SmallLinearMap;
+    ///
+    /// let map: SmallLinearMap<usize, &str, 4> = SmallLinearMap::with_capacity(4);
+    /// assert!(!map.spilled());
+    ///
+    /// let map: SmallLinearMap<usize, &str, 4> = SmallLinearMap::with_capacity(5);
+    /// assert!(map.spilled());
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity <= N {
+            SmallLinearMap::new()
+        } else {
+            SmallLinearMap { storage: Storage::Heap(LinearMap::with_capacity(capacity)) }
+        }
+    }
+
+    /// Returns `true` if the entries have been moved to the heap.
+    pub fn spilled(&self) -> bool {
+        match self.storage {
+            Storage::Inline(_) => false,
+            Storage::Heap(_) => true,
+        }
+    }
+
+    /// Moves the entries back into the inline storage if there are at most `N` of them,
+    /// which frees the heap allocation. Returns `true` if the entries are stored inline
+    /// afterwards.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SmallLinearMap;
+    ///
+    /// let mut map: SmallLinearMap<_, _, 1> = SmallLinearMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "b");
+    /// assert!(!map.shrink_to_inline());
+    ///
+    /// map.remove(&0);
+    /// assert!(map.shrink_to_inline());
+    /// assert_eq!(map.get(&1), Some(&"b"));
+    /// ```
+    pub fn shrink_to_inline(&mut self) -> bool {
+        let len = self.len();
+        if let Storage::Heap(ref mut map) = self.storage {
+            if len > N {
+                return false;
+            }
+            let mut inline = ArrayLinearMap::new();
+            for (key, value) in mem::take(map) {
+                // Cannot fail, the keys are unique and there are at most `N` of them.
+                let _ = inline.try_insert(key, value);
+            }
+            self.storage = Storage::Inline(inline);
+        }
+        true
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        match self.storage {
+            Storage::Inline(ref map) => map.capacity(),
+            Storage::Heap(ref map) => map.capacity(),
+        }
+    }
+
+    /// Clears the map, removing all key-value pairs. A spilled map keeps the allocated
+    /// memory for reuse.
+    pub fn clear(&mut self) {
+        match self.storage {
+            Storage::Inline(ref mut map) => map.clear(),
+            Storage::Heap(ref mut map) => map.clear(),
+        }
+    }
+
+    /// Returns a reference to the requested value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.storage {
+            Storage::Inline(ref map) => map.get(key),
+            Storage::Heap(ref map) => map.get(key),
+        }
+    }
+
+    /// Returns a mutable reference to the requested value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.storage {
+            Storage::Inline(ref mut map) => map.get_mut(key),
+            Storage::Heap(ref mut map) => map.get_mut(key),
+        }
+    }
+
+    /// Returns a tuple with references to the requested key and value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.storage {
+            Storage::Inline(ref map) => map.get_key_value(key),
+            Storage::Heap(ref map) => map.get_key_value(key),
+        }
+    }
+
+    /// Inserts a key-value pair into the map. If the key is new and the inline storage is
+    /// full, all entries are moved to the heap first.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old value
+    /// is returned. The key is not updated, though.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SmallLinearMap;
+    ///
+    /// let mut map: SmallLinearMap<_, _, 1> = SmallLinearMap::new();
+    /// assert_eq!(map.insert(0, "a"), None);
+    /// assert_eq!(map.insert(0, "b"), Some("a"));
+    /// assert!(!map.spilled());
+    /// assert_eq!(map.insert(1, "c"), None);
+    /// assert!(map.spilled());
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (key, value) = match self.storage {
+            Storage::Inline(ref mut map) => match map.try_insert(key, value) {
+                Ok(old) => return old,
+                Err(entry) => entry,
+            },
+            Storage::Heap(ref mut map) => return map.insert(key, value),
+        };
+
+        // The key is not present and the inline storage is full.
+        let mut heap = LinearMap::with_capacity(2 * N.max(1));
+        if let Storage::Inline(ref mut map) = self.storage {
+            for (key, value) in mem::take(map) {
+                heap.keys.push(key);
+                heap.values.push(value);
+            }
+        }
+        heap.keys.push(key);
+        heap.values.push(value);
+        self.storage = Storage::Heap(heap);
+        None
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        match self.storage {
+            Storage::Inline(ref map) => map.len(),
+            Storage::Heap(ref map) => map.len(),
+        }
+    }
+
+    /// Returns if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the entry from the map and returns its value. This is equivalent to
+    /// [`swap_remove`](#method.swap_remove).
+    ///
+    /// Removing entries never moves them back into the inline storage, see
+    /// [`shrink_to_inline`](#method.shrink_to_inline).
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.swap_remove(key)
+    }
+
+    /// Removes the entry from the map and returns its value. The last entry of the map
+    /// takes the place of the removed one.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.swap_remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the entry from the map and returns the stored key and value. The last entry
+    /// of the map takes the place of the removed one.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn swap_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.storage {
+            Storage::Inline(ref mut map) => map.swap_remove_entry(key),
+            Storage::Heap(ref mut map) => map.swap_remove_entry(key),
+        }
+    }
+
+    /// Removes the entry from the map and returns its value. All entries after the removed
+    /// one are moved forward, which preserves the order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.shift_remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the entry from the map and returns the stored key and value. All entries
+    /// after the removed one are moved forward, which preserves the order of the entries.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn shift_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.storage {
+            Storage::Inline(ref mut map) => map.shift_remove_entry(key),
+            Storage::Heap(ref mut map) => map.shift_remove_entry(key),
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Gets an iterator over the keys of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn keys(&self) -> Keys<'_, K> {
+        Keys { iter: self.as_slices().0.iter() }
+    }
+
+    /// Gets an iterator over the values of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn values(&self) -> Values<'_, V> {
+        Values { iter: self.as_slices().1.iter() }
+    }
+
+    /// Gets a mutable iterator over the values of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        ValuesMut { iter: self.as_mut_slices().1.iter_mut() }
+    }
+
+    /// Gets an iterator over the entries of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let (keys, values) = self.as_slices();
+        Iter {
+            key: keys.iter(),
+            value: values.iter(),
+        }
+    }
+
+    /// Gets a mutable iterator over the entries of the map in
+    /// [storage order](struct.LinearMap.html#ordering).
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        let (keys, values) = self.as_mut_slices();
+        IterMut {
+            key: keys.iter(),
+            value: values.iter_mut(),
+        }
+    }
+
+    #[inline]
+    fn as_slices(&self) -> (&[K], &[V]) {
+        match self.storage {
+            Storage::Inline(ref map) => map.as_slices(),
+            Storage::Heap(ref map) => (&map.keys, &map.values),
+        }
+    }
+
+    #[inline]
+    fn as_mut_slices(&mut self) -> (&[K], &mut [V]) {
+        match self.storage {
+            Storage::Inline(ref mut map) => map.as_mut_slices(),
+            Storage::Heap(ref mut map) => (&map.keys, &mut map.values),
+        }
+    }
+}
+
+impl<K, V, const N: usize> Default for SmallLinearMap<K, V, N>
+    where K: PartialEq
+{
+    /// Creates an empty `SmallLinearMap`.
+    fn default() -> Self {
+        SmallLinearMap::new()
+    }
+}
+
+impl<K, V, const N: usize> fmt::Debug for SmallLinearMap<K, V, N>
+    where
+        K: PartialEq + fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Equality ignores the order of the entries and whether either map has spilled to the heap.
+impl<K, V, const N: usize> PartialEq for SmallLinearMap<K, V, N>
+    where
+        K: PartialEq,
+        V: PartialEq,
+{
+    fn eq(&self, other: &SmallLinearMap<K, V, N>) -> bool {
+        self.len() == other.len() &&
+            self.iter().all(|(key, value)| other.get(key).is_some_and(|v| *value == *v))
+    }
+}
+
+impl<K, V, const N: usize> Eq for SmallLinearMap<K, V, N>
+    where
+        K: Eq,
+        V: Eq,
+{
+}
+
+impl<K, V, const N: usize> FromIterator<(K, V)> for SmallLinearMap<K, V, N>
+    where K: PartialEq
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> SmallLinearMap<K, V, N> {
+        let mut map = SmallLinearMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+/// Inserts all entries of the iterator. Entries with a key that is already present replace
+/// the value in the map.
+impl<K, V, const N: usize> Extend<(K, V)> for SmallLinearMap<K, V, N>
+    where K: PartialEq
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize> Extend<(&'a K, &'a V)> for SmallLinearMap<K, V, N>
+    where
+        K: PartialEq + Copy + 'a,
+        V: Copy + 'a,
+{
+    fn extend<T: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+impl<K, Q, V, const N: usize> Index<&Q> for SmallLinearMap<K, V, N>
+    where
+        K: PartialEq + Borrow<Q>,
+        Q: PartialEq + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a SmallLinearMap<K, V, N>
+    where
+        K: PartialEq + 'a,
+        V: 'a
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a mut SmallLinearMap<K, V, N>
+    where
+        K: PartialEq + 'a,
+        V: 'a
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+/// An owning iterator over the entries of a SmallLinearMap.
+///
+/// This struct is created by the `into_iter` method on [`SmallLinearMap`](struct.SmallLinearMap.html). See its documentation for more.
+pub struct IntoIter<K, V, const N: usize> {
+    inner: IntoIterInner<K, V, N>,
+}
+
+enum IntoIterInner<K, V, const N: usize> {
+    Inline(array::IntoIter<K, V, N>),
+    Heap(MapIntoIter<K, V>),
+}
+
+impl<K, V, const N: usize> Iterator for IntoIter<K, V, N> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        match self.inner {
+            IntoIterInner::Inline(ref mut iter) => iter.next(),
+            IntoIterInner::Heap(ref mut iter) => iter.next(),
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> IntoIterator for SmallLinearMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, N>;
+
+    fn into_iter(self) -> IntoIter<K, V, N> {
+        let inner = match self.storage {
+            Storage::Inline(map) => IntoIterInner::Inline(map.into_iter()),
+            Storage::Heap(map) => IntoIterInner::Heap(map.into_iter()),
+        };
+        IntoIter { inner }
+    }
+}
@@ -0,0 +1,569 @@
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FromIterator;
+use core::mem;
+use core::ops::{Bound, Index, RangeBounds};
+
+use alloc::vec::Vec;
+
+use {IntoIter, Iter, IterMut, Keys, LinearMap, Values, ValuesMut};
+
+/// SortedLinearMap is a map for `Ord` keys that stores its entries like
+/// [`LinearMap`](struct.LinearMap.html), in a vector of keys and a vector of values, but
+/// keeps the keys sorted. Lookups use binary search and take O(log n) time, inserting and
+/// removing shift the following entries and take O(n) time. The sorted order also enables
+/// ordered operations like `range` and `pop_first`.
+///
+/// Compared to `BTreeMap`, the entries are stored contiguously, which makes iteration and
+/// small maps fast, but large maps slow to modify.
+///
+/// # Examples
+///
+/// ```
+/// extern crate linear_map;
+/// use linear_map::SortedLinearMap;
+///
+/// let mut map = SortedLinearMap::new();
+/// map.insert(3, "c");
+/// map.insert(1, "a");
+/// map.insert(2, "b");
+///
+/// assert!(map.keys().eq(&[1, 2, 3]));
+/// assert_eq!(map.get(&2), Some(&"b"));
+/// assert_eq!(map.range(2..).count(), 2);
+/// assert_eq!(map.pop_first(), Some((1, "a")));
+/// ```
+#[derive(Clone)]
+pub struct SortedLinearMap<K, V>
+    where K: Ord
+{
+    map: LinearMap<K, V>,
+}
+
+impl<K, V> SortedLinearMap<K, V>
+    where K: Ord
+{
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        SortedLinearMap { map: LinearMap::new() }
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        SortedLinearMap { map: LinearMap::with_capacity(capacity) }
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Clears the map, removing all elements. Keeps the allocated memory for reuse.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(log n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SortedLinearMap;
+    ///
+    /// let mut map = SortedLinearMap::new();
+    /// map.insert("b".to_string(), 2);
+    /// assert_eq!(map.get("b"), Some(&2));
+    /// assert_eq!(map.get("a"), None);
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+    {
+        self.search(key).ok().map(|i| &self.map.values[i])
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(log n) where n is the number of elements in the map.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+    {
+        match self.search(key) {
+            Ok(i) => Some(&mut self.map.values[i]),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns the key-value pair corresponding to the key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(log n) where n is the number of elements in the map.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+    {
+        self.search(key).ok().map(|i| (&self.map.keys[i], &self.map.values[i]))
+    }
+
+    /// Returns true if the map contains a value for the specified key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(log n) where n is the number of elements in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+    {
+        self.search(key).is_ok()
+    }
+
+    /// Inserts a key-value pair into the map at the position that keeps the keys sorted.
+    ///
+    /// If the map did not have this key present, `None` is returned. If the map did have
+    /// this key present, the value is updated, and the old value is returned.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map. O(log n) if the key is present or
+    /// greater than all keys in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SortedLinearMap;
+    ///
+    /// let mut map = SortedLinearMap::new();
+    /// assert_eq!(map.insert(2, "a"), None);
+    /// assert_eq!(map.insert(1, "b"), None);
+    /// assert_eq!(map.insert(2, "c"), Some("a"));
+    /// assert!(map.iter().eq(vec![(&1, &"b"), (&2, &"c")]));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.search(&key) {
+            Ok(i) => Some(mem::replace(&mut self.map.values[i], value)),
+            Err(i) => {
+                self.map.keys.insert(i, key);
+                self.map.values.insert(i, value);
+                None
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously
+    /// in the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key was
+    /// previously in the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+    {
+        match self.search(key) {
+            Ok(i) => Some((self.map.keys.remove(i), self.map.values.remove(i))),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns an iterator over the entries whose keys are in `range`, in ascending order of
+    /// the keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if both are equal and
+    /// excluded.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(log n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SortedLinearMap;
+    ///
+    /// let map: SortedLinearMap<_, _> = (0..10).map(|i| (i, i * 10)).collect();
+    /// assert!(map.range(3..6).eq(vec![(&3, &30), (&4, &40), (&5, &50)]));
+    /// assert_eq!(map.range(..=1).count(), 2);
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+            R: RangeBounds<Q>,
+    {
+        let (start, end) = self.range_indices(range);
+        Iter {
+            key: self.map.keys[start..end].iter(),
+            value: self.map.values[start..end].iter(),
+        }
+    }
+
+    /// Returns an iterator over the entries whose keys are in `range`, with mutable
+    /// references to the values, in ascending order of the keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if both are equal and
+    /// excluded.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(log n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SortedLinearMap;
+    ///
+    /// let mut map: SortedLinearMap<_, _> = (0..5).map(|i| (i, i)).collect();
+    /// for (_, value) in map.range_mut(3..) {
+    ///     *value = 0;
+    /// }
+    /// assert!(map.values().eq(&[0, 1, 2, 0, 0]));
+    /// ```
+    pub fn range_mut<Q, R>(&mut self, range: R) -> IterMut<'_, K, V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+            R: RangeBounds<Q>,
+    {
+        let (start, end) = self.range_indices(range);
+        IterMut {
+            key: self.map.keys[start..end].iter(),
+            value: self.map.values[start..end].iter_mut(),
+        }
+    }
+
+    /// Returns the entry with the smallest key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.map.keys.first().map(|key| (key, &self.map.values[0]))
+    }
+
+    /// Returns the entry with the largest key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.map.keys.last().map(|key| (key, &self.map.values[self.map.values.len() - 1]))
+    }
+
+    /// Removes and returns the entry with the smallest key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SortedLinearMap;
+    ///
+    /// let mut map: SortedLinearMap<_, _> = vec![(2, "b"), (1, "a")].into_iter().collect();
+    /// assert_eq!(map.pop_first(), Some((1, "a")));
+    /// assert_eq!(map.pop_first(), Some((2, "b")));
+    /// assert_eq!(map.pop_first(), None);
+    /// ```
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            None
+        } else {
+            Some((self.map.keys.remove(0), self.map.values.remove(0)))
+        }
+    }
+
+    /// Removes and returns the entry with the largest key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::SortedLinearMap;
+    ///
+    /// let mut map: SortedLinearMap<_, _> = vec![(2, "b"), (1, "a")].into_iter().collect();
+    /// assert_eq!(map.pop_last(), Some((2, "b")));
+    /// assert_eq!(map.pop_last(), Some((1, "a")));
+    /// assert_eq!(map.pop_last(), None);
+    /// ```
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        let key = self.map.keys.pop()?;
+        self.map.values.pop().map(|value| (key, value))
+    }
+
+    /// Returns an iterator over the keys in ascending order.
+    pub fn keys(&self) -> Keys<'_, K> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator over the values in ascending order of their keys.
+    pub fn values(&self) -> Values<'_, V> {
+        self.map.values()
+    }
+
+    /// Returns an iterator over mutable references to the values in ascending order of their
+    /// keys.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        self.map.values_mut()
+    }
+
+    /// Returns an iterator over the key-value pairs in ascending order of the keys.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// Returns an iterator over the key-value pairs with mutable references to the values in
+    /// ascending order of the keys.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Returns the underlying `LinearMap`, whose entries are sorted by key.
+    pub fn as_linear_map(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+
+    /// Converts the map into a `LinearMap` without copying the entries. The entries stay
+    /// sorted until the `LinearMap` is modified.
+    pub fn into_linear_map(self) -> LinearMap<K, V> {
+        self.map
+    }
+
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+    {
+        self.map.keys.binary_search_by(|k| k.borrow().cmp(key))
+    }
+
+    fn range_indices<Q, R>(&self, range: R) -> (usize, usize)
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+            R: RangeBounds<Q>,
+    {
+        match (range.start_bound(), range.end_bound()) {
+            (Bound::Excluded(start), Bound::Excluded(end)) if start == end => {
+                panic!("range start and end are equal and excluded in SortedLinearMap")
+            }
+            (Bound::Included(start), Bound::Included(end))
+            | (Bound::Included(start), Bound::Excluded(end))
+            | (Bound::Excluded(start), Bound::Included(end))
+            | (Bound::Excluded(start), Bound::Excluded(end)) if start > end => {
+                panic!("range start is greater than range end in SortedLinearMap")
+            }
+            _ => {}
+        }
+        let keys = &self.map.keys;
+        let start = match range.start_bound() {
+            Bound::Included(start) => keys.partition_point(|k| k.borrow() < start),
+            Bound::Excluded(start) => keys.partition_point(|k| k.borrow() <= start),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => keys.partition_point(|k| k.borrow() <= end),
+            Bound::Excluded(end) => keys.partition_point(|k| k.borrow() < end),
+            Bound::Unbounded => keys.len(),
+        };
+        (start, end)
+    }
+}
+
+impl<K, V> Default for SortedLinearMap<K, V>
+    where K: Ord
+{
+    /// Creates an empty `SortedLinearMap`.
+    fn default() -> Self {
+        SortedLinearMap::new()
+    }
+}
+
+impl<K, V> fmt::Debug for SortedLinearMap<K, V>
+    where
+        K: Ord + fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+/// Since the entries of both maps are sorted, they are compared in lockstep.
+impl<K, V> PartialEq for SortedLinearMap<K, V>
+    where
+        K: Ord,
+        V: PartialEq,
+{
+    fn eq(&self, other: &SortedLinearMap<K, V>) -> bool {
+        self.map.keys == other.map.keys && self.map.values == other.map.values
+    }
+}
+
+impl<K, V> Eq for SortedLinearMap<K, V>
+    where
+        K: Ord,
+        V: Eq,
+{
+}
+
+/// Sorts the entries of the `LinearMap` by key.
+impl<K, V> From<LinearMap<K, V>> for SortedLinearMap<K, V>
+    where K: Ord
+{
+    fn from(map: LinearMap<K, V>) -> Self {
+        let mut entries: Vec<(K, V)> = map.into_iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        let mut sorted = SortedLinearMap::with_capacity(entries.len());
+        for (key, value) in entries {
+            sorted.map.keys.push(key);
+            sorted.map.values.push(value);
+        }
+        sorted
+    }
+}
+
+impl<K, V> From<SortedLinearMap<K, V>> for LinearMap<K, V>
+    where K: Ord
+{
+    fn from(map: SortedLinearMap<K, V>) -> Self {
+        map.map
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for SortedLinearMap<K, V>
+    where K: Ord
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = SortedLinearMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for SortedLinearMap<K, V>
+    where K: Ord
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.map.keys.reserve(iter.size_hint().0);
+        self.map.values.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> Extend<(&'a K, &'a V)> for SortedLinearMap<K, V>
+    where
+        K: Ord + Copy + 'a,
+        V: Copy + 'a,
+{
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+impl<K, V, Q> Index<&Q> for SortedLinearMap<K, V>
+    where
+        K: Ord + Borrow<Q>,
+        Q: Ord + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SortedLinearMap<K, V>
+    where K: Ord
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut SortedLinearMap<K, V>
+    where K: Ord
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> IntoIterator for SortedLinearMap<K, V>
+    where K: Ord
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
@@ -0,0 +1,341 @@
+//! A read-only map over a `&'static` slice of key-value pairs, usually created with the
+//! [`static_linear_map!`](../macro.static_linear_map.html) macro.
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::ops::Index;
+use core::slice;
+
+use LinearMapRef;
+
+#[cfg(feature = "alloc")]
+use LinearMap;
+
+/// StaticLinearMap is a read-only map that borrows its entries from a `&'static [(K, V)]`,
+/// so it can be built at compile time and stored in a `static` or `const` without
+/// allocating. Keys are searched linearly like in [`LinearMap`](struct.LinearMap.html), and
+/// the entries are visited in the order of the slice.
+///
+/// The [`static_linear_map!`](macro.static_linear_map.html) macro creates the map from
+/// `key => value` pairs and rejects duplicate literal keys at compile time.
+/// [`new`](#method.new) accepts any slice and does not check it for duplicate keys.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate linear_map;
+/// use linear_map::StaticLinearMap;
+///
+/// static STATUS: StaticLinearMap<u16, &str> = static_linear_map! {
+///     200 => "OK",
+///     404 => "Not Found",
+///     500 => "Internal Server Error",
+/// };
+///
+/// # fn main() {
+/// assert_eq!(STATUS.get(&404), Some(&"Not Found"));
+/// assert_eq!(STATUS.get(&418), None);
+/// assert_eq!(STATUS.len(), 3);
+/// # }
+/// ```
+pub struct StaticLinearMap<K: 'static, V: 'static> {
+    entries: &'static [(K, V)],
+}
+
+impl<K, V> StaticLinearMap<K, V> {
+    /// Creates a map over the given entries.
+    ///
+    /// The entries are not checked for duplicate keys. If a key occurs more than once, the
+    /// lookup methods return its first entry, while iterators visit all of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate linear_map;
+    /// use linear_map::StaticLinearMap;
+    ///
+    /// const DIGITS: StaticLinearMap<char, u32> = StaticLinearMap::new(&[('0', 0), ('1', 1)]);
+    /// assert_eq!(DIGITS[&'1'], 1);
+    /// ```
+    pub const fn new(entries: &'static [(K, V)]) -> Self {
+        StaticLinearMap { entries }
+    }
+
+    /// Returns the slice of key-value pairs that the map was created from.
+    pub const fn entries(&self) -> &'static [(K, V)] {
+        self.entries
+    }
+
+    /// Returns the number of elements in the map.
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns if the map is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns a reference to the requested value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'static V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.find(key).map(|(_, v)| v)
+    }
+
+    /// Returns a tuple with references to the requested key and value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&'static K, &'static V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.find(key).map(|(k, v)| (k, v))
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Returns an iterator over the keys in the order of the slice.
+    pub fn keys(&self) -> Keys<K, V> {
+        Keys { iter: self.entries.iter() }
+    }
+
+    /// Returns an iterator over the values in the order of the slice.
+    pub fn values(&self) -> Values<K, V> {
+        Values { iter: self.entries.iter() }
+    }
+
+    /// Returns an iterator over the key-value pairs in the order of the slice.
+    pub fn iter(&self) -> Iter<K, V> {
+        Iter { iter: self.entries.iter() }
+    }
+
+    /// Returns the entries as a [`LinearMapRef`](struct.LinearMapRef.html).
+    pub const fn as_view(&self) -> LinearMapRef<'static, K, V> {
+        LinearMapRef::from_pairs(self.entries)
+    }
+
+    /// Copies the entries into a new [`LinearMap`](struct.LinearMap.html). If a key occurs
+    /// more than once, its first entry is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use]
+    /// extern crate linear_map;
+    ///
+    /// # fn main() {
+    /// let defaults = static_linear_map! { "threads" => 4, "retries" => 3 };
+    ///
+    /// let mut config = defaults.to_linear_map();
+    /// config.insert("threads", 8);
+    /// assert_eq!(config["threads"], 8);
+    /// assert_eq!(defaults["threads"], 4);
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_linear_map(&self) -> LinearMap<K, V>
+        where
+            K: PartialEq + Clone,
+            V: Clone,
+    {
+        let mut map = LinearMap::with_capacity(self.len());
+        for (k, v) in self.entries {
+            map.entry(k.clone()).or_insert_with(|| v.clone());
+        }
+        map
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<&'static (K, V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.entries.iter().find(|&(k, _)| key.eq(k.borrow()))
+    }
+}
+
+/// Used by [`static_linear_map!`](../macro.static_linear_map.html) to give the closure that
+/// checks for duplicate keys the key type of the map.
+#[doc(hidden)]
+pub const fn __check_keys<K, V, F>(_map: &StaticLinearMap<K, V>, _check: &F)
+    where F: Fn(&K)
+{
+}
+
+impl<K, V> Clone for StaticLinearMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for StaticLinearMap<K, V> {}
+
+impl<K, V> Default for StaticLinearMap<K, V> {
+    fn default() -> Self {
+        StaticLinearMap::new(&[])
+    }
+}
+
+impl<K, V> fmt::Debug for StaticLinearMap<K, V>
+    where
+        K: fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// The order of the entries is ignored. Duplicate keys count towards the length, but only
+/// the first entry of a key is compared.
+impl<K, V> PartialEq for StaticLinearMap<K, V>
+    where
+        K: PartialEq,
+        V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K, V> Eq for StaticLinearMap<K, V>
+    where
+        K: Eq,
+        V: Eq,
+{
+}
+
+impl<K, V, Q> Index<&Q> for StaticLinearMap<K, V>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<K, V> IntoIterator for StaticLinearMap<K, V> {
+    type Item = (&'static K, &'static V);
+    type IntoIter = Iter<K, V>;
+
+    fn into_iter(self) -> Iter<K, V> {
+        self.iter()
+    }
+}
+
+impl<K, V> IntoIterator for &StaticLinearMap<K, V> {
+    type Item = (&'static K, &'static V);
+    type IntoIter = Iter<K, V>;
+
+    fn into_iter(self) -> Iter<K, V> {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a StaticLinearMap.
+///
+/// This struct is created by the `iter` method on
+/// [`StaticLinearMap`](../struct.StaticLinearMap.html). See its documentation for more.
+pub struct Iter<K: 'static, V: 'static> {
+    iter: slice::Iter<'static, (K, V)>,
+}
+
+impl<K, V> Iterator for Iter<K, V> {
+    type Item = (&'static K, &'static V);
+
+    fn next(&mut self) -> Option<(&'static K, &'static V)> {
+        self.iter.next().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<K, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+/// An iterator over the keys of a StaticLinearMap.
+///
+/// This struct is created by the `keys` method on
+/// [`StaticLinearMap`](../struct.StaticLinearMap.html). See its documentation for more.
+pub struct Keys<K: 'static, V: 'static> {
+    iter: slice::Iter<'static, (K, V)>,
+}
+
+impl<K, V> Iterator for Keys<K, V> {
+    type Item = &'static K;
+
+    fn next(&mut self) -> Option<&'static K> {
+        self.iter.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<K, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+/// An iterator over the values of a StaticLinearMap.
+///
+/// This struct is created by the `values` method on
+/// [`StaticLinearMap`](../struct.StaticLinearMap.html). See its documentation for more.
+pub struct Values<K: 'static, V: 'static> {
+    iter: slice::Iter<'static, (K, V)>,
+}
+
+impl<K, V> Iterator for Values<K, V> {
+    type Item = &'static V;
+
+    fn next(&mut self) -> Option<&'static V> {
+        self.iter.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<K, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
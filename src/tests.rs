@@ -1,6 +1,44 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
+#[allow(clippy::module_inception)]
 mod tests {
-    use LinearMap;
+    use std::string::String;
+    use std::vec::Vec;
+    use std::cell::Cell;
+    use std::cmp::Reverse;
+    #[cfg(feature = "std")]
+    use std::boxed::Box;
+    #[cfg(feature = "std")]
+    use std::error::Error;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+    use std::string::ToString;
+    use {FromPartsErrorKind, LinearMap, Entry};
+
+    /// Increments the shared counter when dropped.
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    thread_local! {
+        static PANIC_ON_EQ: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// A key whose comparison panics for the key `0` once `PANIC_ON_EQ` is set.
+    #[derive(Debug)]
+    struct PanickingKey(usize);
+
+    impl PartialEq for PanickingKey {
+        fn eq(&self, other: &PanickingKey) -> bool {
+            if (self.0 == 0 || other.0 == 0) && PANIC_ON_EQ.with(|p| p.get()) {
+                panic!("comparing key 0");
+            }
+            self.0 == other.0
+        }
+    }
 
     #[test]
     fn new() {
@@ -240,4 +278,1951 @@ mod tests {
         assert_eq!(iter.next(), Some((&1, &String::from("orld!"))));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn entry_or_insert() {
+        let mut map = LinearMap::new();
+        for word in "one two two three three three".split(' ') {
+            *map.entry(word).or_insert(0) += 1;
+        }
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get("one"), Some(&1));
+        assert_eq!(map.get("two"), Some(&2));
+        assert_eq!(map.get("three"), Some(&3));
+    }
+
+    #[test]
+    fn entry_and_modify() {
+        let mut map = LinearMap::new();
+        map.insert(0, String::from("Hello"));
+        map.entry(0).and_modify(|v| v.push('!')).or_insert_with(String::new);
+        map.entry(1).and_modify(|v| v.push('!')).or_insert_with(|| String::from("World"));
+        assert_eq!(map.get(&0), Some(&String::from("Hello!")));
+        assert_eq!(map.get(&1), Some(&String::from("World")));
+    }
+
+    #[test]
+    fn entry_occupied() {
+        let mut map = LinearMap::new();
+        map.insert(0, "Hello");
+        map.insert(1, "World!");
+        match map.entry(0) {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.key(), &0);
+                assert_eq!(entry.insert("Bye"), "Hello");
+                assert_eq!(entry.remove_entry(), (0, "Bye"));
+            }
+            Entry::Vacant(_) => panic!("expected an occupied entry"),
+        }
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&1), Some(&"World!"));
+    }
+
+    #[test]
+    fn entry_vacant() {
+        let mut map = LinearMap::new();
+        map.insert(0, "Hello");
+        match map.entry(1) {
+            Entry::Occupied(_) => panic!("expected a vacant entry"),
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.key(), &1);
+                *entry.insert("World") = "World!";
+            }
+        }
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&1), Some(&"World!"));
+    }
+
+    #[test]
+    fn append_into_empty() {
+        let mut map1 = LinearMap::new();
+        let mut map2 = LinearMap::new();
+        map2.insert(0, String::from("Hello"));
+        map2.insert(1, String::from("World!"));
+
+        map1.append(&mut map2);
+
+        assert!(map2.is_empty());
+        assert_eq!(map1.len(), 2);
+        assert_eq!(map1.get(&0), Some(&String::from("Hello")));
+        assert_eq!(map1.get(&1), Some(&String::from("World!")));
+    }
+
+    #[test]
+    fn append_empty() {
+        let mut map1: LinearMap<_, _> = (0..3).map(|i| (i, i)).collect();
+        let capacity = map1.capacity();
+        map1.append(&mut LinearMap::new());
+        assert_eq!(map1.keys_slice(), [0, 1, 2]);
+        assert_eq!(map1.capacity(), capacity);
+    }
+
+    #[test]
+    fn append_drops_each_value_once() {
+        let drops = Rc::new(Cell::new(0));
+        {
+            let mut map1 = LinearMap::new();
+            map1.insert(0, DropCounter(drops.clone()));
+            map1.insert(1, DropCounter(drops.clone()));
+
+            let mut map2 = LinearMap::new();
+            map2.insert(1, DropCounter(drops.clone()));
+            map2.insert(2, DropCounter(drops.clone()));
+
+            map1.append(&mut map2);
+
+            // The value replaced by the entry from map2 is dropped.
+            assert_eq!(drops.get(), 1);
+            assert_eq!(map1.len(), 3);
+            assert!(map2.is_empty());
+        }
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn append_panicking_eq() {
+        let drops = Rc::new(Cell::new(0));
+        let mut map1 = LinearMap::new();
+        map1.insert(PanickingKey(1), DropCounter(drops.clone()));
+
+        let mut map2 = LinearMap::new();
+        map2.insert(PanickingKey(2), DropCounter(drops.clone()));
+        map2.insert(PanickingKey(0), DropCounter(drops.clone()));
+        map2.insert(PanickingKey(3), DropCounter(drops.clone()));
+
+        PANIC_ON_EQ.with(|p| p.set(true));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| map1.append(&mut map2)));
+        PANIC_ON_EQ.with(|p| p.set(false));
+        assert!(result.is_err());
+
+        // The panicking entry and the entry after it were dropped together with map2.
+        assert_eq!(drops.get(), 2);
+        assert!(map2.is_empty());
+        assert_eq!(map1.len(), 2);
+        assert_eq!(map1.keys().count(), map1.values().count());
+        assert!(map1.contains_key(&PanickingKey(2)));
+
+        drop(map1);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn iterators() {
+        let mut map: LinearMap<_, _> = (0..4).map(|i| (i, i * 10)).collect();
+
+        let mut iter = map.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some((&0, &0)));
+        assert_eq!(iter.next_back(), Some((&3, &30)));
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(format!("{:?}", iter), "[(1, 10), (2, 20)]");
+        assert_eq!(iter.clone().count(), 2);
+        assert_eq!(iter.by_ref().rev().collect::<Vec<_>>(), vec![(&2, &20), (&1, &10)]);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut iter = map.iter_mut();
+        assert_eq!(iter.len(), 4);
+        if let Some((_, v)) = iter.next_back() {
+            *v += 1;
+        }
+        assert_eq!(format!("{:?}", iter), "[(0, 0), (1, 10), (2, 20)]");
+        assert_eq!(map[&3], 31);
+
+        assert!(map.keys().rev().eq(&[3, 2, 1, 0]));
+        assert_eq!(map.keys().len(), 4);
+        assert_eq!(format!("{:?}", map.keys()), "[0, 1, 2, 3]");
+        assert!(map.values().rev().eq(&[31, 20, 10, 0]));
+        let mut values = map.values();
+        values.nth(2);
+        assert_eq!(format!("{:?}", values), "[31]");
+        for v in map.values_mut().rev().take(1) {
+            *v = 30;
+        }
+        assert_eq!(map.values_mut().len(), 4);
+        assert_eq!(format!("{:?}", map.values_mut()), "[0, 10, 20, 30]");
+
+        let mut iter = map.clone().into_iter();
+        assert_eq!(iter.next_back(), Some((3, 30)));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(format!("{:?}", iter), "[(0, 0), (1, 10), (2, 20)]");
+        assert_eq!(iter.clone().rev().collect::<Vec<_>>(), vec![(2, 20), (1, 10), (0, 0)]);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(0, 0), (1, 10), (2, 20)]);
+
+        let mut drain = map.drain();
+        assert_eq!(drain.len(), 4);
+        assert_eq!(drain.next_back(), Some((3, 30)));
+        assert_eq!(format!("{:?}", drain), "[(0, 0), (1, 10), (2, 20)]");
+        drop(drain);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn into_keys_and_values() {
+        let drops = Rc::new(Cell::new(0));
+        let map: LinearMap<_, _> = (0..3).map(|i| (i, DropCounter(drops.clone()))).collect();
+        let mut keys = map.into_keys();
+        assert_eq!(drops.get(), 3);
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys.next_back(), Some(2));
+        assert_eq!(format!("{:?}", keys), "[0, 1]");
+        assert_eq!(keys.clone().collect::<Vec<_>>(), vec![0, 1]);
+
+        let keys: Vec<_> = (0..3).map(Rc::new).collect();
+        let weak_keys: Vec<_> = keys.iter().map(Rc::downgrade).collect();
+        let map: LinearMap<_, _> = keys.into_iter().zip(0..3).collect();
+        let mut values = map.into_values();
+        assert!(weak_keys.iter().all(|key| key.upgrade().is_none()));
+        assert_eq!(values.size_hint(), (3, Some(3)));
+        assert_eq!(values.next(), Some(0));
+        assert_eq!(format!("{:?}", values), "[1, 2]");
+        assert_eq!(values.rev().collect::<Vec<_>>(), vec![2, 1]);
+    }
+
+    #[test]
+    fn parts() {
+        let mut map = LinearMap::with_capacity(10);
+        map.insert("a", 1);
+        map.insert("b", 2);
+        let (keys, values) = map.into_parts();
+        assert_eq!(keys, ["a", "b"]);
+        assert_eq!(values, [1, 2]);
+        assert!(keys.capacity() >= 10);
+
+        let map = LinearMap::from_parts(keys, values).unwrap();
+        assert!(map.capacity() >= 10);
+        assert_eq!(map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(), vec![("a", 1), ("b", 2)]);
+
+        let map = LinearMap::<u8, u8>::from_parts(Vec::new(), Vec::new()).unwrap();
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn from_parts_errors() {
+        let error = LinearMap::from_parts(vec![1, 2, 3], vec!["a", "b"]).unwrap_err();
+        assert_eq!(error.kind(), FromPartsErrorKind::LengthMismatch);
+        assert_eq!(error.to_string(), "3 keys and 2 values have different lengths");
+        assert_eq!(format!("{:?}", error), "FromPartsError { kind: LengthMismatch, keys: 3, values: 2 }");
+        assert_eq!(error.keys(), [1, 2, 3]);
+        assert_eq!(error.values(), ["a", "b"]);
+        assert_eq!(error.clone().into_parts(), (vec![1, 2, 3], vec!["a", "b"]));
+
+        let error = LinearMap::from_parts(vec![1, 2, 3, 2, 1], vec![0; 5]).unwrap_err();
+        assert_eq!(error.kind(), FromPartsErrorKind::DuplicateKey { index: 3 });
+        assert_eq!(error.to_string(), "duplicate key at index 3");
+        #[cfg(feature = "std")]
+        {
+            let error: Box<dyn Error> = Box::new(error);
+            assert_eq!(error.to_string(), "duplicate key at index 3");
+        }
+    }
+
+    #[test]
+    fn slices() {
+        let mut map: LinearMap<_, _> = (0..4).map(|i| (i, i * 10)).collect();
+        map.swap_remove(&0);
+        assert_eq!(map.keys_slice(), [3, 1, 2]);
+        assert_eq!(map.values_slice(), [30, 10, 20]);
+        map.values_mut_slice().sort();
+        assert_eq!(map.values_slice(), [10, 20, 30]);
+        assert_eq!(map[&3], 10);
+        assert!(LinearMap::<u8, u8>::new().keys_slice().is_empty());
+    }
+
+    #[test]
+    fn positions() {
+        let mut map: LinearMap<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+        assert_eq!(map.get_index_of(&3), Some(3));
+        assert_eq!(map.get_index_of(&5), None);
+        assert_eq!(map.get_index(4), Some((&4, &40)));
+        assert_eq!(map.get_index(5), None);
+        *map.get_index_mut(0).unwrap().1 = 1;
+        assert_eq!(map.get_index_mut(5), None);
+        assert_eq!(map[&0], 1);
+
+        map.swap_indices(0, 4);
+        assert_eq!(map.keys_slice(), [4, 1, 2, 3, 0]);
+        assert_eq!(map.values_slice(), [40, 10, 20, 30, 1]);
+        map.swap_indices(2, 2);
+
+        map.move_index(0, 3);
+        assert_eq!(map.keys_slice(), [1, 2, 3, 4, 0]);
+        map.move_index(4, 0);
+        assert_eq!(map.keys_slice(), [0, 1, 2, 3, 4]);
+        map.move_index(2, 2);
+        assert_eq!(map.values_slice(), [1, 10, 20, 30, 40]);
+
+        assert_eq!(map.remove_index(1), Some((1, 10)));
+        assert_eq!(map.keys_slice(), [0, 4, 2, 3]);
+        assert_eq!(map.shift_remove_index(1), Some((4, 40)));
+        assert_eq!(map.keys_slice(), [0, 2, 3]);
+        assert_eq!(map.swap_remove_index(2), Some((3, 30)));
+        assert_eq!(map.remove_index(2), None);
+        assert_eq!(map.shift_remove_index(2), None);
+        assert_eq!(map.values_slice(), [1, 20]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_indices_out_of_bounds() {
+        let mut map: LinearMap<_, _> = (0..2).map(|i| (i, i)).collect();
+        map.swap_indices(0, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn move_index_out_of_bounds() {
+        let mut map: LinearMap<_, _> = (0..2).map(|i| (i, i)).collect();
+        map.move_index(2, 0);
+    }
+
+    #[test]
+    fn sort() {
+        let keys = [5, 3, 8, 0, 9, 1, 7, 2, 6, 4];
+        let mut map: LinearMap<_, _> = keys.iter().map(|&k| (k, k * 10)).collect();
+        map.sort_keys();
+        assert_eq!(map.keys_slice(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(map.values_slice(), [0, 10, 20, 30, 40, 50, 60, 70, 80, 90]);
+
+        map.reverse();
+        assert_eq!(map.keys_slice(), [9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+        assert_eq!(map.values_slice(), [90, 80, 70, 60, 50, 40, 30, 20, 10, 0]);
+
+        map.sort_by(|_, v1, _, v2| (v1 % 3).cmp(&(v2 % 3)));
+        assert_eq!(map.keys_slice(), [9, 6, 3, 0, 7, 4, 1, 8, 5, 2]);
+
+        map.sort_unstable_by_key(|&k, _| Reverse(k));
+        assert_eq!(map.keys_slice(), [9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+        assert!(map.iter().all(|(k, v)| *v == k * 10));
+
+        let mut empty: LinearMap<i32, i32> = LinearMap::new();
+        empty.sort_keys();
+        empty.reverse();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn sort_panic() {
+        let mut map: LinearMap<_, _> = (0..5).rev().map(|i| (i, i)).collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            map.sort_by(|k1, _, k2, _| if *k1 == 2 { panic!() } else { k1.cmp(k2) });
+        }));
+        assert!(result.is_err());
+        assert_eq!(map.keys_slice(), [4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn get_many_mut() {
+        let mut map: LinearMap<String, i32> = (0..4).map(|i| (i.to_string(), i)).collect();
+        {
+            let [a, b, c] = map.get_many_mut(["3", "0", "2"]).unwrap();
+            *a += 10;
+            *b += 20;
+            *c += 30;
+        }
+        assert_eq!(map.values_slice(), [20, 1, 32, 13]);
+        assert_eq!(map.get_many_mut(["1", "4"]), None);
+        assert_eq!(map.get_many_mut(["1", "2", "1"]), None);
+        assert_eq!(map.get_many_mut::<str, 0>([]), Some([]));
+        assert_eq!(map.get_many_mut(["1"]), Some([&mut 1]));
+
+        unsafe {
+            let [a, b] = map.get_many_unchecked_mut(["1", "2"]).unwrap();
+            *a = *b;
+            assert_eq!(map.get_many_unchecked_mut(["1", "4"]), None);
+        }
+        assert_eq!(map["1"], 32);
+    }
+
+    #[test]
+    fn capacity() {
+        let mut map: LinearMap<u32, u64> = LinearMap::new();
+        assert_eq!(map.capacity(), 0);
+        map.reserve(10);
+        assert!(map.capacity() >= 10);
+        map.reserve_exact(20);
+        assert!(map.capacity() >= 20);
+        map.insert(1, 1);
+        map.shrink_to(5);
+        assert!(map.capacity() >= 5 && map.capacity() < 20);
+        map.shrink_to_fit();
+        assert!(map.capacity() >= 1 && map.capacity() < 5);
+        map.shrink_to(10);
+        assert!(map.capacity() < 5);
+        assert_eq!(map[&1], 1);
+
+        // The values of zero-sized types never need to allocate.
+        let map: LinearMap<u32, ()> = LinearMap::with_capacity(3);
+        assert_eq!(map.capacity(), 3);
+        let map: LinearMap<(), u32> = LinearMap::with_capacity(3);
+        assert_eq!(map.capacity(), 3);
+    }
+
+    #[test]
+    fn try_reserve() {
+        let mut map: LinearMap<u32, u64> = LinearMap::new();
+        assert!(map.try_reserve(10).is_ok());
+        assert!(map.capacity() >= 10);
+        assert!(map.try_reserve_exact(30).is_ok());
+        assert!(map.capacity() >= 30);
+
+        let capacity = map.capacity();
+        assert!(map.try_reserve(usize::MAX).is_err());
+        assert!(map.try_reserve_exact(usize::MAX / 2).is_err());
+        assert_eq!(map.capacity(), capacity);
+
+        // Reserving the keys succeeds, reserving the values fails.
+        let mut map: LinearMap<(), u64> = LinearMap::with_capacity(2);
+        assert!(map.try_reserve(usize::MAX / 4).is_err());
+        assert!(map.try_reserve_exact(usize::MAX / 4).is_err());
+        assert_eq!(map.capacity(), 2);
+        map.insert((), 1);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut map: LinearMap<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+        let tail = map.split_off(3);
+        assert_eq!(map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(), vec![(0, 0), (1, 10), (2, 20)]);
+        assert_eq!(tail.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(), vec![(3, 30), (4, 40)]);
+        assert!(map.split_off(3).is_empty());
+        let all = map.split_off(0);
+        assert!(map.is_empty());
+        assert_eq!(all.len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_out_of_bounds() {
+        let mut map: LinearMap<_, _> = (0..2).map(|i| (i, i)).collect();
+        map.split_off(3);
+    }
+
+    #[test]
+    fn retain() {
+        let drops = Rc::new(Cell::new(0));
+        let mut map: LinearMap<_, _> = (0..10).map(|i| (i, DropCounter(drops.clone()))).collect();
+        let mut visited = Vec::new();
+        map.retain(|&k, _| {
+            visited.push(k);
+            k % 3 == 0
+        });
+        assert_eq!(visited, (0..10).collect::<Vec<_>>());
+        assert!(map.keys().eq(&[0, 3, 6, 9]));
+        assert_eq!(drops.get(), 6);
+
+        map.retain(|_, _| true);
+        assert_eq!(map.len(), 4);
+        map.retain(|_, _| false);
+        assert!(map.is_empty());
+        assert_eq!(drops.get(), 10);
+
+        let mut map: LinearMap<_, _> = (0..4).map(|i| (i, i)).collect();
+        map.retain(|_, v| {
+            *v *= 2;
+            true
+        });
+        assert!(map.values().eq(&[0, 2, 4, 6]));
+        map.retain_mut(|&k, v| {
+            *v += 1;
+            k != 2
+        });
+        assert!(map.values().eq(&[1, 3, 7]));
+    }
+
+    #[test]
+    fn retain_panicking_predicate() {
+        let drops = Rc::new(Cell::new(0));
+        let mut map: LinearMap<_, _> = (0..6).map(|i| (i, DropCounter(drops.clone()))).collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            map.retain(|&k, _| {
+                if k == 3 {
+                    panic!("predicate");
+                }
+                k % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+
+        // 1 was removed, 3 and everything after it were retained.
+        assert_eq!(drops.get(), 1);
+        assert!(map.keys().eq(&[0, 2, 3, 4, 5]));
+        assert_eq!(map.values().count(), 5);
+        map.insert(1, DropCounter(drops.clone()));
+        drop(map);
+        assert_eq!(drops.get(), 7);
+    }
+
+    #[test]
+    fn drain() {
+        let drops = Rc::new(Cell::new(0));
+        let mut map = LinearMap::with_capacity(8);
+        for i in 0..4 {
+            map.insert(i, DropCounter(drops.clone()));
+        }
+        assert_eq!(map.drain().map(|(k, _)| k).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(drops.get(), 4);
+        assert!(map.is_empty());
+        assert!(map.capacity() >= 8);
+
+        for i in 0..4 {
+            map.insert(i, DropCounter(drops.clone()));
+        }
+        {
+            let mut drain = map.drain();
+            assert_eq!(drain.next().map(|(k, _)| k), Some(0));
+        }
+        assert_eq!(drops.get(), 8);
+        assert!(map.is_empty());
+        map.insert(5, DropCounter(drops.clone()));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn extract_if() {
+        let mut map: LinearMap<_, _> = (0..8).map(|i| (i, i)).collect();
+        let extracted: Vec<_> = map.extract_if(|k, v| {
+            *v += 100;
+            k % 2 == 1
+        }).collect();
+        assert_eq!(extracted, vec![(1, 101), (3, 103), (5, 105), (7, 107)]);
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![(0, 100), (2, 102), (4, 104), (6, 106)]);
+
+        // Entries that were not visited are retained.
+        let mut map: LinearMap<_, _> = (0..8).map(|i| (i, i)).collect();
+        {
+            let mut iter = map.extract_if(|k, _| k % 2 == 1);
+            assert_eq!(iter.next(), Some((1, 1)));
+            assert_eq!(iter.next(), Some((3, 3)));
+        }
+        assert!(map.keys().eq(&[0, 2, 4, 5, 6, 7]));
+        assert!(map.values().eq(&[0, 2, 4, 5, 6, 7]));
+
+        let mut map: LinearMap<_, _> = (0..3).map(|i| (i, i)).collect();
+        assert_eq!(map.extract_if(|_, _| false).count(), 0);
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.extract_if(|_, _| true).count(), 3);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn extract_if_panicking_predicate() {
+        let drops = Rc::new(Cell::new(0));
+        let mut map: LinearMap<_, _> = (0..6).map(|i| (i, DropCounter(drops.clone()))).collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            map.extract_if(|&k, _| {
+                if k == 4 {
+                    panic!("predicate");
+                }
+                k < 3
+            }).count()
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 3);
+        assert!(map.keys().eq(&[3, 4, 5]));
+        drop(map);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn extract_if_leaked() {
+        let mut map: LinearMap<_, _> = (0..4).map(|i| (i, i)).collect();
+        ::core::mem::forget(map.extract_if(|_, _| true));
+        assert!(map.is_empty());
+        map.insert(1, 1);
+        assert_eq!(map[&1], 1);
+    }
+
+    #[test]
+    fn debug() {
+        let mut map = LinearMap::new();
+        map.insert(0, "Hello");
+        map.insert(1, "World!");
+        assert_eq!(format!("{:?}", map), r#"{0: "Hello", 1: "World!"}"#);
+        assert_eq!(format!("{:?}", LinearMap::<u8, u8>::new()), "{}");
+    }
+
+    #[test]
+    fn clone() {
+        let mut map = LinearMap::new();
+        map.insert(0, String::from("Hello"));
+        let mut clone = map.clone();
+        clone.get_mut(&0).unwrap().push('!');
+        assert_eq!(map.get(&0), Some(&String::from("Hello")));
+        assert_eq!(clone.get(&0), Some(&String::from("Hello!")));
+    }
+
+    #[test]
+    fn eq_ignores_order() {
+        let mut map1 = LinearMap::new();
+        map1.insert(0, "Hello");
+        map1.insert(1, "World!");
+
+        let mut map2 = LinearMap::new();
+        map2.insert(1, "World!");
+        map2.insert(0, "Hello");
+        assert_eq!(map1, map2);
+
+        map2.insert(1, "foo");
+        assert!(map1 != map2);
+
+        map2.remove(&1);
+        assert!(map1 != map2);
+        assert_eq!(LinearMap::<u8, u8>::new(), LinearMap::default());
+    }
+
+    #[test]
+    fn from_iter() {
+        let map: LinearMap<_, _> = vec![(0, "Hello"), (1, "World!"), (0, "foo")].into_iter().collect();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&0), Some(&"foo"));
+        assert_eq!(map.get(&1), Some(&"World!"));
+    }
+
+    #[test]
+    fn extend() {
+        let mut map = LinearMap::new();
+        map.insert(0, 10);
+        map.extend(vec![(1, 11), (2, 12)]);
+        map.extend(vec![(&2, &22), (&3, &23)]);
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.get(&1), Some(&11));
+        assert_eq!(map.get(&2), Some(&22));
+        assert_eq!(map.get(&3), Some(&23));
+    }
+
+    #[test]
+    fn index() {
+        let mut map = LinearMap::new();
+        map.insert(String::from("Hello"), 0);
+        map.insert(String::from("World!"), 1);
+        assert_eq!(map["Hello"], 0);
+        assert_eq!(map["World!"], 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_missing_key() {
+        let mut map = LinearMap::new();
+        map.insert(0, "Hello");
+        let _ = map[&1];
+    }
+
+    #[test]
+    fn insert_keeps_order() {
+        let mut map = LinearMap::new();
+        map.insert(2, "a");
+        map.insert(0, "b");
+        map.insert(1, "c");
+        map.insert(0, "d");
+        assert!(map.keys().eq(&[2, 0, 1]));
+        assert!(map.values().eq(&["a", "d", "c"]));
+    }
+
+    #[test]
+    fn swap_remove() {
+        let mut map = LinearMap::new();
+        map.insert(0, "a");
+        map.insert(1, "b");
+        map.insert(2, "c");
+        assert_eq!(map.swap_remove(&0), Some("a"));
+        assert_eq!(map.swap_remove(&0), None);
+        assert!(map.iter().eq(vec![(&2, &"c"), (&1, &"b")]));
+        assert_eq!(map.swap_remove_entry(&2), Some((2, "c")));
+        assert!(map.iter().eq(vec![(&1, &"b")]));
+    }
+
+    #[test]
+    fn shift_remove() {
+        let mut map = LinearMap::new();
+        map.insert(0, "a");
+        map.insert(1, "b");
+        map.insert(2, "c");
+        map.insert(3, "d");
+        assert_eq!(map.shift_remove(&1), Some("b"));
+        assert_eq!(map.shift_remove(&1), None);
+        assert!(map.iter().eq(vec![(&0, &"a"), (&2, &"c"), (&3, &"d")]));
+        assert_eq!(map.shift_remove_entry(&0), Some((0, "a")));
+        assert!(map.iter().eq(vec![(&2, &"c"), (&3, &"d")]));
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod set {
+    use std::vec::Vec;
+    use LinearSet;
+
+    fn set(values: &[i32]) -> LinearSet<i32> {
+        values.iter().cloned().collect()
+    }
+
+    #[test]
+    fn insert() {
+        let mut set = LinearSet::new();
+        assert!(set.insert(0));
+        assert!(set.insert(1));
+        assert!(!set.insert(0));
+        assert_eq!(set.len(), 2);
+        assert!(set.iter().eq(&[0, 1]));
+    }
+
+    #[test]
+    fn contains_and_get() {
+        let set = set(&[0, 1]);
+        assert!(set.contains(&0));
+        assert!(!set.contains(&2));
+        assert_eq!(set.get(&1), Some(&1));
+        assert_eq!(set.get(&2), None);
+    }
+
+    #[test]
+    fn remove() {
+        let mut set = set(&[0, 1, 2, 3]);
+        assert!(set.remove(&0));
+        assert!(!set.remove(&0));
+        assert!(set.iter().eq(&[3, 1, 2]));
+        assert!(set.shift_remove(&3));
+        assert!(set.iter().eq(&[1, 2]));
+        assert_eq!(set.take(&1), Some(1));
+        assert_eq!(set.take(&1), None);
+    }
+
+    #[test]
+    fn into_iter() {
+        let set = set(&[0, 1]);
+        assert!(set.into_iter().eq(vec![0, 1]));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[1, 2, 3]);
+        let b = set(&[2, 3, 4]);
+        assert_eq!(a.union(&b).cloned().collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert_eq!(a.intersection(&b).cloned().collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(a.difference(&b).cloned().collect::<Vec<_>>(), [1]);
+        assert_eq!(a.symmetric_difference(&b).cloned().collect::<Vec<_>>(), [1, 4]);
+    }
+
+    #[test]
+    fn iterators() {
+        let a = set(&[1, 2, 3]);
+        let b = set(&[2, 3, 4, 5]);
+        let mut iter = a.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(format!("{:?}", iter), "[1, 2]");
+        assert!(a.iter().rev().eq(&[3, 2, 1]));
+
+        let mut iter = a.clone().into_iter();
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(format!("{:?}", iter), "[1, 2]");
+
+        assert_eq!(a.union(&b).size_hint(), (4, Some(7)));
+        assert_eq!(a.intersection(&b).size_hint(), (0, Some(3)));
+        assert_eq!(a.difference(&b).size_hint(), (0, Some(3)));
+        assert_eq!(b.difference(&set(&[1])).size_hint(), (3, Some(4)));
+        assert_eq!(a.symmetric_difference(&b).size_hint(), (1, Some(7)));
+        assert_eq!(format!("{:?}", a.union(&b)), "[1, 2, 3, 4, 5]");
+        assert_eq!(format!("{:?}", a.intersection(&b)), "[2, 3]");
+        assert_eq!(format!("{:?}", a.difference(&b)), "[1]");
+        assert_eq!(format!("{:?}", a.symmetric_difference(&b)), "[1, 4, 5]");
+
+        let mut difference = a.difference(&b);
+        assert_eq!(difference.next(), Some(&1));
+        assert_eq!(difference.next(), None);
+        assert_eq!(difference.next(), None);
+    }
+
+    #[test]
+    fn subset_and_superset() {
+        let a = set(&[1, 2, 3]);
+        let b = set(&[3, 1]);
+        assert!(b.is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert!(a.is_superset(&b));
+        assert!(!b.is_superset(&a));
+        assert!(a.is_subset(&a));
+        assert!(!a.is_disjoint(&b));
+        assert!(a.is_disjoint(&set(&[4])));
+    }
+
+    #[test]
+    fn traits() {
+        let mut a = set(&[1, 2]);
+        assert_eq!(a, set(&[2, 1]));
+        assert!(a != set(&[1]));
+        assert_eq!(format!("{:?}", a), "{1, 2}");
+        a.extend(&[3, 1]);
+        assert_eq!(a.clone(), set(&[1, 2, 3]));
+        assert_eq!(LinearSet::<i32>::default(), set(&[]));
+    }
+}
+
+#[cfg(test)]
+mod array {
+    use std::string::String;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use ArrayLinearMap;
+
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn new() {
+        let map = ArrayLinearMap::<usize, usize, 4>::new();
+        assert!(map.is_empty());
+        assert!(!map.is_full());
+        assert_eq!(map.len(), 0);
+        assert_eq!(map.capacity(), 4);
+    }
+
+    #[test]
+    fn insert_and_get() {
+        let mut map = ArrayLinearMap::<_, _, 2>::new();
+        assert_eq!(map.insert(0, "Hello"), None);
+        assert_eq!(map.insert(1, "World!"), None);
+        assert_eq!(map.insert(1, "foo"), Some("World!"));
+        assert!(map.is_full());
+        assert_eq!(map.get(&0), Some(&"Hello"));
+        assert_eq!(map.get(&1), Some(&"foo"));
+        assert_eq!(map.get(&2), None);
+        assert_eq!(map.get_key_value(&0), Some((&0, &"Hello")));
+        *map.get_mut(&0).unwrap() = "ello";
+        assert_eq!(map[&0], "ello");
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_into_full() {
+        let mut map = ArrayLinearMap::<_, _, 1>::new();
+        map.insert(0, "Hello");
+        map.insert(1, "World!");
+    }
+
+    #[test]
+    fn try_insert() {
+        let mut map = ArrayLinearMap::<_, _, 1>::new();
+        assert_eq!(map.try_insert(0, "Hello"), Ok(None));
+        assert_eq!(map.try_insert(1, "World!"), Err((1, "World!")));
+        assert_eq!(map.try_insert(0, "foo"), Ok(Some("Hello")));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn remove() {
+        let mut map: ArrayLinearMap<_, _, 4> = vec![(0, "a"), (1, "b"), (2, "c"), (3, "d")].into_iter().collect();
+        assert_eq!(map.remove(&0), Some("a"));
+        assert!(map.keys().eq(&[3, 1, 2]));
+        assert_eq!(map.shift_remove(&3), Some("d"));
+        assert!(map.keys().eq(&[1, 2]));
+        assert_eq!(map.shift_remove_entry(&1), Some((1, "b")));
+        assert_eq!(map.swap_remove_entry(&2), Some((2, "c")));
+        assert_eq!(map.remove(&2), None);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn iter() {
+        let mut map = ArrayLinearMap::<_, _, 4>::new();
+        map.insert(0, String::from("Hello"));
+        map.insert(1, String::from("World!"));
+        for (_, v) in &mut map {
+            v.remove(0);
+        }
+        assert!(map.iter().eq(vec![(&0, &String::from("ello")), (&1, &String::from("orld!"))]));
+        assert!(map.values().eq(&[String::from("ello"), String::from("orld!")]));
+        assert!(map.into_iter().eq(vec![(0, String::from("ello")), (1, String::from("orld!"))]));
+    }
+
+    #[test]
+    fn traits() {
+        let mut map1 = ArrayLinearMap::<_, _, 4>::new();
+        map1.insert(0, "Hello");
+        map1.insert(1, "World!");
+        let mut map2 = ArrayLinearMap::default();
+        map2.extend(vec![(&1, &"World!"), (&0, &"Hello")]);
+        assert_eq!(map1, map2);
+        assert_eq!(map1.clone(), map1);
+        assert_eq!(format!("{:?}", map1), r#"{0: "Hello", 1: "World!"}"#);
+    }
+
+    #[test]
+    fn drops() {
+        let drops = Rc::new(Cell::new(0));
+        {
+            let mut map = ArrayLinearMap::<_, _, 4>::new();
+            for i in 0..4 {
+                map.insert(i, DropCounter(drops.clone()));
+            }
+            map.remove(&0);
+            assert_eq!(drops.get(), 1);
+            map.shift_remove(&1);
+            assert_eq!(drops.get(), 2);
+
+            let mut iter = map.into_iter();
+            iter.next();
+            assert_eq!(drops.get(), 3);
+        }
+        assert_eq!(drops.get(), 4);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod small {
+    use std::string::String;
+    use SmallLinearMap;
+
+    #[test]
+    fn new() {
+        let map = SmallLinearMap::<usize, usize, 2>::new();
+        assert!(map.is_empty());
+        assert!(!map.spilled());
+        assert_eq!(map.capacity(), 2);
+    }
+
+    #[test]
+    fn with_capacity() {
+        assert!(!SmallLinearMap::<usize, usize, 2>::with_capacity(2).spilled());
+        let map = SmallLinearMap::<usize, usize, 2>::with_capacity(10);
+        assert!(map.spilled());
+        assert!(map.capacity() >= 10);
+    }
+
+    #[test]
+    fn insert_spills() {
+        let mut map = SmallLinearMap::<_, _, 2>::new();
+        map.insert(0, String::from("a"));
+        map.insert(1, String::from("b"));
+        assert_eq!(map.insert(1, String::from("c")), Some(String::from("b")));
+        assert!(!map.spilled());
+
+        map.insert(2, String::from("d"));
+        assert!(map.spilled());
+        assert_eq!(map.len(), 3);
+        assert!(map.keys().eq(&[0, 1, 2]));
+        assert_eq!(map.get(&0), Some(&String::from("a")));
+        assert_eq!(map.get(&1), Some(&String::from("c")));
+        assert_eq!(map[&2], "d");
+    }
+
+    #[test]
+    fn insert_spills_zero_capacity() {
+        let mut map = SmallLinearMap::<_, _, 0>::new();
+        map.insert(0, "a");
+        assert!(map.spilled());
+        assert_eq!(map.get(&0), Some(&"a"));
+    }
+
+    #[test]
+    fn remove() {
+        let mut map: SmallLinearMap<_, _, 2> = vec![(0, "a"), (1, "b"), (2, "c"), (3, "d")].into_iter().collect();
+        assert_eq!(map.remove(&0), Some("a"));
+        assert!(map.keys().eq(&[3, 1, 2]));
+        assert_eq!(map.shift_remove(&3), Some("d"));
+        assert!(map.keys().eq(&[1, 2]));
+        assert!(map.spilled());
+        assert_eq!(map.swap_remove_entry(&1), Some((1, "b")));
+        assert_eq!(map.shift_remove_entry(&1), None);
+    }
+
+    #[test]
+    fn shrink_to_inline() {
+        let mut map: SmallLinearMap<_, _, 2> = vec![(0, "a"), (1, "b"), (2, "c")].into_iter().collect();
+        assert!(!map.shrink_to_inline());
+        assert!(map.spilled());
+
+        map.shift_remove(&0);
+        assert!(map.shrink_to_inline());
+        assert!(!map.spilled());
+        assert!(map.iter().eq(vec![(&1, &"b"), (&2, &"c")]));
+        assert!(map.shrink_to_inline());
+    }
+
+    #[test]
+    fn iter() {
+        let mut map: SmallLinearMap<_, _, 1> = vec![(0, 1), (1, 2)].into_iter().collect();
+        for (_, v) in &mut map {
+            *v *= 10;
+        }
+        for v in map.values_mut() {
+            *v += 1;
+        }
+        assert!(map.values().eq(&[11, 21]));
+        assert!(map.into_iter().eq(vec![(0, 11), (1, 21)]));
+
+        let map: SmallLinearMap<_, _, 4> = vec![(0, 1), (1, 2)].into_iter().collect();
+        assert!(map.into_iter().eq(vec![(0, 1), (1, 2)]));
+    }
+
+    #[test]
+    fn eq_ignores_spilling() {
+        let mut map1 = SmallLinearMap::<_, _, 1>::new();
+        map1.extend(vec![(&0, &"a"), (&1, &"b")]);
+        map1.remove(&1);
+        let mut map2 = SmallLinearMap::<_, _, 1>::default();
+        map2.insert(0, "a");
+        assert!(map1.spilled());
+        assert!(!map2.spilled());
+        assert_eq!(map1, map2);
+        assert_eq!(map1.clone(), map1);
+        assert_eq!(format!("{:?}", map1), r#"{0: "a"}"#);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod adaptive {
+    use std::vec::Vec;
+    use AdaptiveMap;
+
+    #[test]
+    fn new() {
+        let map = AdaptiveMap::<usize, usize>::new();
+        assert!(map.is_empty());
+        assert!(!map.is_hashed());
+        assert_eq!(map.threshold(), ::adaptive::DEFAULT_THRESHOLD);
+    }
+
+    #[test]
+    fn promotes_and_demotes() {
+        let mut map = AdaptiveMap::with_threshold(4);
+        for i in 0..4 {
+            map.insert(i, i * 10);
+        }
+        assert!(!map.is_hashed());
+        assert_eq!(map.insert(3, 33), Some(30));
+        assert!(!map.is_hashed());
+
+        map.insert(4, 40);
+        assert!(map.is_hashed());
+        assert_eq!(map.len(), 5);
+        for i in 0..3 {
+            assert_eq!(map.get(&i), Some(&(i * 10)));
+        }
+        assert_eq!(map[&3], 33);
+
+        // Hysteresis: stays hashed until less than half of the threshold remains.
+        map.remove(&4);
+        map.remove(&3);
+        assert_eq!(map.remove_entry(&2), Some((2, 20)));
+        assert!(map.is_hashed());
+        assert_eq!(map.remove(&1), Some(10));
+        assert!(!map.is_hashed());
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&0), Some(&0));
+        assert_eq!(map.get(&1), None);
+    }
+
+    #[test]
+    fn demotes_with_small_threshold() {
+        for &threshold in &[0, 1] {
+            let mut map = AdaptiveMap::with_threshold(threshold);
+            map.insert(0, 0);
+            map.insert(1, 10);
+            assert!(map.is_hashed());
+            map.remove(&1);
+            assert!(map.is_hashed());
+            assert_eq!(map.remove_entry(&0), Some((0, 0)));
+            assert!(!map.is_hashed());
+            assert!(map.is_empty());
+        }
+    }
+
+    #[test]
+    fn clear() {
+        let mut map: AdaptiveMap<_, _> = (0..100).map(|i| (i, i)).collect();
+        assert!(map.is_hashed());
+        map.clear();
+        assert!(map.is_empty());
+        assert!(!map.is_hashed());
+    }
+
+    #[test]
+    fn iter() {
+        for &n in &[4, 100] {
+            let mut map: AdaptiveMap<_, _> = (0..n).map(|i| (i, i)).collect();
+            for (_, v) in &mut map {
+                *v += 1;
+            }
+            for v in map.values_mut() {
+                *v *= 2;
+            }
+            let mut entries: Vec<_> = map.iter().map(|(&k, &v)| (k, v)).collect();
+            entries.sort();
+            assert_eq!(entries, (0..n).map(|i| (i, (i + 1) * 2)).collect::<Vec<_>>());
+            assert_eq!(map.keys().count(), n);
+            assert_eq!(map.values().count(), n);
+            assert_eq!(map.iter().len(), n);
+            assert_eq!(map.iter_mut().skip(1).len(), n - 1);
+            assert_eq!(map.keys().size_hint(), (n, Some(n)));
+            assert_eq!(map.values().len(), n);
+            assert_eq!(map.values_mut().len(), n);
+            let mut iter = map.into_iter();
+            assert_eq!(iter.len(), n);
+            let mut entries: Vec<_> = iter.by_ref().collect();
+            assert_eq!(iter.next(), None);
+            entries.sort();
+            assert_eq!(entries.len(), n);
+        }
+    }
+
+    #[test]
+    fn eq_ignores_storage() {
+        let mut map1: AdaptiveMap<_, _> = (0..20).map(|i| (i, i)).collect();
+        for i in 2..20 {
+            map1.remove(&i);
+        }
+        let mut map2 = AdaptiveMap::with_threshold(1);
+        map2.extend(vec![(&1, &1), (&0, &0)]);
+        assert!(map2.is_hashed());
+        assert!(!map1.is_hashed());
+        assert_eq!(map1, map2);
+        assert_eq!(map1.clone(), map1);
+    }
+}
+
+#[cfg(all(test, feature = "serde", feature = "alloc"))]
+mod serde {
+    extern crate postcard;
+    extern crate serde_json;
+
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    use de::{self, DuplicateKeys, LinearMapSeed};
+    use serde::de::DeserializeSeed;
+    use {LinearMap, LinearSet};
+
+    const DUPLICATES: &str = r#"{"a": 1, "b": 2, "a": 3}"#;
+
+    fn deserialize_with(duplicate_keys: DuplicateKeys) -> Result<LinearMap<String, u32>, serde_json::Error> {
+        let mut deserializer = serde_json::Deserializer::from_str(DUPLICATES);
+        LinearMapSeed::new(duplicate_keys).deserialize(&mut deserializer)
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut map = LinearMap::new();
+        map.insert("b".to_string(), 2);
+        map.insert("a".to_string(), 1);
+        map.insert("c".to_string(), 3);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"b":2,"a":1,"c":3}"#);
+        let map2: LinearMap<String, u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(map2.iter().collect::<Vec<_>>(), map.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn postcard_round_trip() {
+        let map: LinearMap<u32, Vec<i64>> = (0..10).map(|i| (i * 7, vec![-(i as i64); i as usize])).collect();
+        let bytes = postcard::to_allocvec(&map).unwrap();
+        let map2: LinearMap<u32, Vec<i64>> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(map2.iter().collect::<Vec<_>>(), map.iter().collect::<Vec<_>>());
+
+        let empty: LinearMap<u32, u32> = LinearMap::new();
+        let bytes = postcard::to_allocvec(&empty).unwrap();
+        assert!(postcard::from_bytes::<LinearMap<u32, u32>>(&bytes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let map: LinearMap<String, u32> = serde_json::from_str(DUPLICATES).unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], 3);
+
+        let map = deserialize_with(DuplicateKeys::LastWins).unwrap();
+        assert_eq!(map["a"], 3);
+        assert_eq!(map["b"], 2);
+
+        let map = deserialize_with(DuplicateKeys::FirstWins).unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], 1);
+        assert_eq!(map["b"], 2);
+
+        let err = deserialize_with(DuplicateKeys::Error).unwrap_err();
+        assert!(err.to_string().contains("duplicate key"));
+    }
+
+    #[test]
+    fn deserialize_with_functions() {
+        let mut deserializer = serde_json::Deserializer::from_str(DUPLICATES);
+        let map: LinearMap<String, u32> = de::first_wins(&mut deserializer).unwrap();
+        assert_eq!(map["a"], 1);
+
+        let mut deserializer = serde_json::Deserializer::from_str(DUPLICATES);
+        assert!(de::deny_duplicates::<_, String, u32>(&mut deserializer).is_err());
+
+        let mut deserializer = serde_json::Deserializer::from_str(r#"{"a": 1, "b": 2}"#);
+        assert_eq!(de::deny_duplicates::<_, String, u32>(&mut deserializer).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn set_round_trip() {
+        let set: LinearSet<u8> = vec![3, 1, 2].into_iter().collect();
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(json, "[3,1,2]");
+        let set2: LinearSet<u8> = serde_json::from_str("[3,1,3,2,1]").unwrap();
+        assert_eq!(set2.iter().collect::<Vec<_>>(), vec![&3, &1, &2]);
+
+        let bytes = postcard::to_allocvec(&set).unwrap();
+        let set3: LinearSet<u8> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(set3, set);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod int {
+    use std::vec::Vec;
+    use {int, IntKey, IntLinearMap, LinearMap};
+
+    fn check_find<K: IntKey + ::core::fmt::Debug>(keys: &[K], absent: K) {
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(int::find(keys, key), Some(i), "{:?} in {:?}", key, keys);
+            assert_eq!(int::find(&keys[..i], key), None);
+        }
+        assert_eq!(int::find(keys, &absent), None);
+    }
+
+    #[test]
+    fn find_all_widths() {
+        // Lengths around the vector sizes exercise the AVX2, SSE2 and scalar paths.
+        for n in 0..100 {
+            check_find(&(0..n as u8).collect::<Vec<_>>(), 255);
+            check_find(&(0..n as i16).map(|i| -i).collect::<Vec<_>>(), 1);
+            check_find(&(0..n as u32).map(|i| i << 20).collect::<Vec<_>>(), 1);
+            check_find(&(0..n as u64).map(|i| i * 0x0101_0101_0101).collect::<Vec<_>>(), 7);
+            check_find(&(0..n as u32).filter_map(::core::char::from_u32).collect::<Vec<_>>(), '\u{10FFFF}');
+        }
+    }
+
+    fn check_find_long<K: IntKey + ::core::fmt::Debug>(zero: K, one: K, two: K) {
+        // Haystacks of at least 512 bytes take the AVX2 path if it is available.
+        for &len in &[512, 600, 1100] {
+            let mut keys = vec![zero; len];
+            assert_eq!(int::find(&keys, &one), None);
+            for &i in &[0, 15, 31, 32, 500, len - 17, len - 1] {
+                keys[i] = one;
+                assert_eq!(int::find(&keys, &one), Some(i));
+                keys[i] = zero;
+            }
+            keys[len - 1] = two;
+            assert_eq!(int::find(&keys, &two), Some(len - 1));
+        }
+    }
+
+    #[test]
+    fn find_long_haystacks() {
+        check_find_long(0u8, 1, 255);
+        check_find_long(0u16, 1 << 8, 1);
+        check_find_long(0i32, -1, 1 << 24);
+        check_find_long(0u64, 1 << 56, 1);
+    }
+
+    #[test]
+    fn find_ignores_matches_across_lanes() {
+        // The bytes 01 01 appear between the two keys, but not within one.
+        let keys: Vec<u16> = (0..40).map(|i| if i % 2 == 0 { 0x0100 } else { 0x0001 }).collect();
+        assert_eq!(int::find(&keys, &0x0101), None);
+
+        for &len in &[40, 100] {
+            let mut keys = vec![0x0000_0000_ffff_ffffu64; len];
+            keys.push(0xffff_ffff_0000_0000);
+            assert_eq!(int::find(&keys, &0xffff_ffff_ffff_ffff), None);
+            assert_eq!(int::find(&keys, &0xffff_ffff_0000_0000), Some(len));
+        }
+
+        let mut keys = vec![0x00ff_ffffu32; 50];
+        keys[37] = 0x00ff_ffff;
+        keys[45] = 0xffff_ffff;
+        assert_eq!(int::find(&keys, &0xffff_ffff), Some(45));
+    }
+
+    #[test]
+    fn find_returns_first_match() {
+        let mut keys = vec![0i8; 64];
+        keys[20] = 5;
+        keys[50] = 5;
+        assert_eq!(int::find(&keys, &5), Some(20));
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let mut map = IntLinearMap::new();
+        for i in 0..200u32 {
+            assert_eq!(map.insert(i * 3, i), None);
+        }
+        assert_eq!(map.insert(0, 7), Some(0));
+        for i in 1..200u32 {
+            assert_eq!(map.get(&(i * 3)), Some(&i));
+            assert_eq!(map.get(&(i * 3 + 1)), None);
+        }
+        *map.get_mut(&3).unwrap() += 10;
+        assert_eq!(map.get_key_value(&3), Some((&3, &11)));
+        assert_eq!(map.remove(&0), Some(7));
+        assert_eq!(map.shift_remove(&3), Some(11));
+        assert!(!map.contains_key(&0));
+        assert_eq!(map.len(), 198);
+        *map.entry(6).or_insert(0) += 1;
+        *map.entry(1).or_insert(0) += 1;
+        assert_eq!(map[&6], 3);
+        assert_eq!(map[&1], 1);
+    }
+
+    #[test]
+    fn conversions() {
+        let linear: LinearMap<_, _> = (0..50i64).map(|i| (i, -i)).collect();
+        let map = IntLinearMap::from(linear.clone());
+        assert_eq!(map.as_linear_map(), &linear);
+        assert_eq!(map.iter().collect::<Vec<_>>(), linear.iter().collect::<Vec<_>>());
+        let other: IntLinearMap<_, _> = (0..50i64).rev().map(|i| (i, -i)).collect();
+        assert_eq!(map, other);
+        assert_eq!(LinearMap::from(other), linear);
+        assert_eq!(map.into_linear_map(), linear);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod fingerprint {
+    use std::cell::Cell;
+    use std::hash::{BuildHasher, Hash, Hasher};
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+    use fingerprint::{FxBuildHasher, FxHasher};
+    use {FingerprintLinearMap, LinearMap};
+
+    thread_local! {
+        static EQ_CALLS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(Debug)]
+    struct CountingKey(u32);
+
+    impl PartialEq for CountingKey {
+        fn eq(&self, other: &CountingKey) -> bool {
+            EQ_CALLS.with(|calls| calls.set(calls.get() + 1));
+            self.0 == other.0
+        }
+    }
+
+    impl Hash for CountingKey {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state);
+        }
+    }
+
+    /// Gives every key the same fingerprint.
+    #[derive(Clone, Default)]
+    struct ConstantBuildHasher;
+
+    impl BuildHasher for ConstantBuildHasher {
+        type Hasher = ConstantHasher;
+
+        fn build_hasher(&self) -> ConstantHasher {
+            ConstantHasher
+        }
+    }
+
+    struct ConstantHasher;
+
+    impl Hasher for ConstantHasher {
+        fn write(&mut self, _: &[u8]) {}
+
+        fn finish(&self) -> u64 {
+            0
+        }
+    }
+
+    #[test]
+    fn skips_keys_with_other_fingerprints() {
+        let map: FingerprintLinearMap<_, _> = (0..200).map(|i| (CountingKey(i), i)).collect();
+        EQ_CALLS.with(|calls| calls.set(0));
+        for i in 0..200 {
+            assert_eq!(map.get(&CountingKey(i)), Some(&i));
+        }
+        for i in 200..400 {
+            assert_eq!(map.get(&CountingKey(i)), None);
+        }
+        // Without fingerprints, this would take about 60000 comparisons.
+        let calls = EQ_CALLS.with(|calls| calls.get());
+        assert!(calls < 1000, "{} comparisons", calls);
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let mut map = FingerprintLinearMap::new();
+        for i in 0..100 {
+            assert_eq!(map.insert(i.to_string(), i), None);
+        }
+        assert_eq!(map.insert("7".to_string(), 70), Some(7));
+        assert_eq!(map.len(), 100);
+        assert_eq!(map["7"], 70);
+        *map.get_mut("8").unwrap() += 1;
+        assert_eq!(map.get_key_value("8"), Some((&"8".to_string(), &9)));
+        assert_eq!(map.remove("0"), Some(0));
+        assert_eq!(map.shift_remove("1"), Some(1));
+        assert_eq!(map.swap_remove_entry("2"), Some(("2".to_string(), 2)));
+        assert_eq!(map.shift_remove_entry("3"), Some(("3".to_string(), 3)));
+        assert_eq!(map.remove("0"), None);
+        assert_eq!(map.len(), 96);
+        // The fingerprints must have moved along with the keys.
+        for i in 4..100 {
+            assert!(map.contains_key(&*i.to_string()), "{}", i);
+        }
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.get("4"), None);
+    }
+
+    #[test]
+    fn equal_fingerprints() {
+        let mut map = FingerprintLinearMap::with_hasher(ConstantBuildHasher);
+        for i in 0..50 {
+            map.insert(i, i * 2);
+        }
+        for i in 0..50 {
+            assert_eq!(map.get(&i), Some(&(i * 2)));
+        }
+        assert_eq!(map.get(&50), None);
+        assert_eq!(map.remove(&10), Some(20));
+        assert_eq!(map.get(&49), Some(&98));
+    }
+
+    #[test]
+    fn conversions() {
+        let linear: LinearMap<String, usize> = (0..20).map(|i| (i.to_string(), i)).collect();
+        let map: FingerprintLinearMap<_, _> = linear.clone().into();
+        assert_eq!(map.get("13"), Some(&13));
+        assert_eq!(map.iter().collect::<Vec<_>>(), linear.iter().collect::<Vec<_>>());
+        let reversed: FingerprintLinearMap<_, _, FxBuildHasher> = linear.clone().into_iter().collect::<Vec<_>>()
+            .into_iter().rev().collect();
+        assert_eq!(map, reversed);
+        assert_eq!(LinearMap::from(map), linear);
+        assert_eq!(reversed.into_linear_map().len(), 20);
+    }
+
+    #[test]
+    fn fx_hasher() {
+        let hash = |bytes: &[u8]| {
+            let mut hasher = FxHasher::default();
+            hasher.write(bytes);
+            hasher.finish()
+        };
+        assert_eq!(hash(&[]), 0);
+        assert_ne!(hash(b"abc"), hash(b"abd"));
+        assert_ne!(hash(b"0123456789"), hash(b"0123456788"));
+        assert_eq!(hash(b"0123456789"), hash(b"0123456789"));
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod self_organizing {
+    use std::vec::Vec;
+    use self_organizing::Policy;
+    use {LinearMap, SelfOrganizingLinearMap};
+
+    fn map(policy: Policy) -> SelfOrganizingLinearMap<i32, i32> {
+        let mut map = SelfOrganizingLinearMap::with_policy(policy);
+        for i in 0..5 {
+            map.insert(i, i * 10);
+        }
+        map
+    }
+
+    fn assert_lockstep(map: &SelfOrganizingLinearMap<i32, i32>) {
+        for (k, v) in map.iter() {
+            assert_eq!(*v, k * 10);
+        }
+    }
+
+    #[test]
+    fn move_to_front() {
+        let mut map = map(Policy::MoveToFront);
+        assert_eq!(map.get(&3), Some(&30));
+        assert!(map.keys().eq(&[3, 0, 1, 2, 4]));
+        assert_eq!(map.get_key_value(&4), Some((&4, &40)));
+        assert!(map.keys().eq(&[4, 3, 0, 1, 2]));
+        assert_eq!(map.get_mut(&4), Some(&mut 40));
+        assert!(map.keys().eq(&[4, 3, 0, 1, 2]));
+        assert_eq!(map.get(&5), None);
+        assert!(map.keys().eq(&[4, 3, 0, 1, 2]));
+        assert_lockstep(&map);
+    }
+
+    #[test]
+    fn transpose() {
+        let mut map = map(Policy::Transpose);
+        assert_eq!(map.get(&3), Some(&30));
+        assert!(map.keys().eq(&[0, 1, 3, 2, 4]));
+        map.get(&3);
+        map.get(&3);
+        map.get(&3);
+        assert!(map.keys().eq(&[3, 0, 1, 2, 4]));
+        assert_eq!(map.insert(4, 40), Some(40));
+        assert!(map.keys().eq(&[3, 0, 1, 4, 2]));
+        assert_lockstep(&map);
+    }
+
+    #[test]
+    fn peek_keeps_order() {
+        let mut map = map(Policy::MoveToFront);
+        assert_eq!(map.peek(&4), Some(&40));
+        assert_eq!(map.peek_key_value(&3), Some((&3, &30)));
+        *map.peek_mut(&2).unwrap() += 0;
+        assert!(map.contains_key(&1));
+        assert!(map.keys().eq(&[0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn remove_keeps_order() {
+        let mut map = map(Policy::MoveToFront);
+        map.get(&2);
+        assert_eq!(map.remove(&0), Some(0));
+        assert_eq!(map.remove_entry(&3), Some((3, 30)));
+        assert_eq!(map.remove(&3), None);
+        assert!(map.keys().eq(&[2, 1, 4]));
+        assert_lockstep(&map);
+    }
+
+    #[test]
+    fn skewed_accesses() {
+        for &policy in &[Policy::MoveToFront, Policy::Transpose] {
+            let linear: LinearMap<_, _> = (0..100).map(|i| (i, i * 10)).collect();
+            let mut map = SelfOrganizingLinearMap::from(linear);
+            map.set_policy(policy);
+            for round in 0..200 {
+                map.get(&99);
+                map.get(&(round % 100));
+            }
+            let front: Vec<_> = map.keys().take(2).cloned().collect();
+            assert!(front.contains(&99), "{:?} {:?}", policy, front);
+            assert_eq!(map.len(), 100);
+            assert_lockstep(&map);
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        let mut map = map(Policy::MoveToFront);
+        map.get(&4);
+        let linear: LinearMap<_, _> = map.clone().into();
+        assert!(linear.keys().eq(&[4, 0, 1, 2, 3]));
+        let other: SelfOrganizingLinearMap<_, _> = (0..5).rev().map(|i| (i, i * 10)).collect();
+        assert_eq!(map, other);
+        assert_eq!(other.policy(), Policy::MoveToFront);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod sorted {
+    use std::ops::Bound;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+    use {LinearMap, SortedLinearMap};
+
+    fn map() -> SortedLinearMap<i32, i32> {
+        vec![5, 1, 9, 3, 7].into_iter().map(|i| (i, i * 10)).collect()
+    }
+
+    #[test]
+    fn insert_keeps_keys_sorted() {
+        let mut map = map();
+        assert!(map.keys().eq(&[1, 3, 5, 7, 9]));
+        assert!(map.values().eq(&[10, 30, 50, 70, 90]));
+        assert_eq!(map.insert(4, 40), None);
+        assert_eq!(map.insert(0, 0), None);
+        assert_eq!(map.insert(10, 100), None);
+        assert_eq!(map.insert(5, 55), Some(50));
+        assert!(map.keys().eq(&[0, 1, 3, 4, 5, 7, 9, 10]));
+        assert!(map.values().eq(&[0, 10, 30, 40, 55, 70, 90, 100]));
+    }
+
+    #[test]
+    fn get_remove() {
+        let mut map = map();
+        assert_eq!(map.get(&3), Some(&30));
+        assert_eq!(map.get(&4), None);
+        *map.get_mut(&9).unwrap() += 1;
+        assert_eq!(map.get_key_value(&9), Some((&9, &91)));
+        assert!(map.contains_key(&1));
+        assert_eq!(map.remove(&1), Some(10));
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.remove_entry(&7), Some((7, 70)));
+        assert!(map.keys().eq(&[3, 5, 9]));
+        assert_eq!(map[&5], 50);
+
+        let mut map: SortedLinearMap<String, i32> = SortedLinearMap::new();
+        map.insert("b".to_string(), 2);
+        map.insert("a".to_string(), 1);
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(map.remove("b"), Some(2));
+    }
+
+    #[test]
+    fn range() {
+        let map = map();
+        let keys = |range: (Bound<i32>, Bound<i32>)| map.range(range).map(|(k, _)| *k).collect::<Vec<_>>();
+        assert_eq!(map.range(3..7).map(|(k, _)| *k).collect::<Vec<_>>(), [3, 5]);
+        assert_eq!(map.range(3..=7).map(|(k, _)| *k).collect::<Vec<_>>(), [3, 5, 7]);
+        assert_eq!(map.range(4..).map(|(k, _)| *k).collect::<Vec<_>>(), [5, 7, 9]);
+        assert_eq!(map.range(..4).map(|(k, _)| *k).collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(map.range(..).count(), 5);
+        assert_eq!(map.range(10..20).count(), 0);
+        assert_eq!(map.range(4..4).count(), 0);
+        assert_eq!(keys((Bound::Excluded(3), Bound::Excluded(9))), [5, 7]);
+        assert_eq!(keys((Bound::Excluded(3), Bound::Included(9))), [5, 7, 9]);
+        assert_eq!(keys((Bound::Excluded(0), Bound::Unbounded)), [1, 3, 5, 7, 9]);
+        assert!(map.range(5..=5).eq(vec![(&5, &50)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn range_inverted() {
+        map().range((Bound::Included(7), Bound::Excluded(3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "range start and end are equal and excluded")]
+    fn range_excluded_equal() {
+        map().range((Bound::Excluded(3), Bound::Excluded(3)));
+    }
+
+    #[test]
+    fn range_mut() {
+        let mut map = map();
+        for (k, v) in map.range_mut(2..8) {
+            *v = -k;
+        }
+        assert!(map.values().eq(&[10, -3, -5, -7, 90]));
+    }
+
+    #[test]
+    fn first_last() {
+        let mut map = map();
+        assert_eq!(map.first_key_value(), Some((&1, &10)));
+        assert_eq!(map.last_key_value(), Some((&9, &90)));
+        assert_eq!(map.pop_first(), Some((1, 10)));
+        assert_eq!(map.pop_last(), Some((9, 90)));
+        assert!(map.keys().eq(&[3, 5, 7]));
+        map.clear();
+        assert_eq!(map.first_key_value(), None);
+        assert_eq!(map.last_key_value(), None);
+        assert_eq!(map.pop_first(), None);
+        assert_eq!(map.pop_last(), None);
+    }
+
+    #[test]
+    fn conversions() {
+        let linear: LinearMap<_, _> = vec![(3, "c"), (1, "a"), (2, "b")].into_iter().collect();
+        let map = SortedLinearMap::from(linear);
+        assert!(map.iter().eq(vec![(&1, &"a"), (&2, &"b"), (&3, &"c")]));
+        let other: SortedLinearMap<_, _> = vec![(2, "b"), (3, "c"), (1, "a")].into_iter().collect();
+        assert_eq!(map, other);
+        let linear: LinearMap<_, _> = map.into();
+        assert!(linear.keys().eq(&[1, 2, 3]));
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod frozen {
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+    use frozen::{Lookup, Scan, Sorted};
+    use LinearMap;
+
+    fn map(n: usize) -> LinearMap<String, usize> {
+        (0..n).rev().map(|i| (i.to_string(), i)).collect()
+    }
+
+    fn check<I: Lookup<String, str> + Lookup<String, String>>(frozen: &::FrozenLinearMap<String, usize, I>, n: usize) {
+        assert_eq!(frozen.len(), n);
+        for i in 0..n {
+            assert_eq!(frozen.get(&*i.to_string()), Some(&i));
+            assert_eq!(frozen.get(&i.to_string()), Some(&i));
+            assert_eq!(frozen[&*i.to_string()], i);
+        }
+        assert_eq!(frozen.get("x"), None);
+        assert!(!frozen.contains_key(&*n.to_string()));
+        assert_eq!(frozen.get_key_value("0").map(|(k, v)| (k.as_str(), *v)), if n > 0 { Some(("0", 0)) } else { None });
+        assert!(frozen.iter().all(|(k, v)| *k == v.to_string()));
+    }
+
+    #[test]
+    fn lookups() {
+        // Sizes below and above the linear scan threshold.
+        for &n in &[0, 1, 5, 8, 9, 100] {
+            check(&map(n).freeze_scan(), n);
+            check(&map(n).freeze(), n);
+            check(&map(n).freeze_hashed(), n);
+        }
+    }
+
+    #[test]
+    fn order() {
+        let keys = |map: LinearMap<String, usize>| map.keys().cloned().collect::<Vec<_>>();
+        let expected = keys(map(20));
+        assert_eq!(map(20).freeze_scan().keys().cloned().collect::<Vec<_>>(), expected);
+        assert_eq!(map(20).freeze_hashed().keys().cloned().collect::<Vec<_>>(), expected);
+        let mut sorted = expected.clone();
+        sorted.sort();
+        assert_eq!(map(20).freeze().keys().cloned().collect::<Vec<_>>(), sorted);
+        let thawed = map(20).freeze().thaw();
+        assert_eq!(keys(thawed), sorted);
+        assert_eq!(keys(map(20).freeze_hashed().thaw()), expected);
+    }
+
+    #[test]
+    fn thaw() {
+        let mut map = map(10).freeze_hashed().thaw();
+        map.insert("10".to_string(), 10);
+        assert_eq!(map.len(), 11);
+        assert_eq!(map.get("3"), Some(&3));
+    }
+
+    #[test]
+    fn eq() {
+        assert_eq!(map(30).freeze_scan(), map(30).freeze());
+        assert_eq!(map(30).freeze(), map(30).freeze_hashed());
+        assert!(map(30).freeze_scan() != map(31).freeze_scan());
+        let _: Scan = *map(3).freeze_scan().lookup();
+        let _: Sorted = *map(3).freeze().lookup();
+        assert_eq!(std::format!("{:?}", map(2).freeze()), r#"{"0": 0, "1": 1}"#);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod macros {
+    use std::string::String;
+    use std::vec::Vec;
+    use std::sync::Mutex;
+    use {LinearMap, LinearSet, StaticLinearMap};
+
+    static STATUS: StaticLinearMap<u16, &str> = static_linear_map! {
+        200 => "OK",
+        404 => "Not Found",
+        500 => "Internal Server Error",
+    };
+
+    const BIG: StaticLinearMap<u64, bool> = static_linear_map! { 5_000_000_000 => true, 0 => false };
+    const NEGATIVE: StaticLinearMap<i8, char> = static_linear_map! { -1 => 'a', 1 => 'b' };
+    const FLAGS: StaticLinearMap<bool, &str> = static_linear_map! { true => "yes", false => "no" };
+    const EMPTY: StaticLinearMap<char, u8> = static_linear_map! {};
+
+    const ONE: u8 = 1;
+    const PATHS: StaticLinearMap<u8, &str> = static_linear_map! { ONE => "one", ONE + 1 => "two" };
+    const DUPLICATES: StaticLinearMap<u8, &str> = static_linear_map! { ONE => "first", ONE => "second" };
+
+    static REGISTRY: Mutex<LinearMap<String, u32>> = Mutex::new(LinearMap::new());
+    static SET: Mutex<LinearSet<u32>> = Mutex::new(LinearSet::new());
+
+    #[test]
+    fn linear_map() {
+        let map = linear_map! { "a" => 1, "b" => 2, "c" => 3 };
+        assert_eq!(map.len(), 3);
+        assert!(map.capacity() >= 3);
+        assert_eq!(map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(), vec![("a", 1), ("b", 2), ("c", 3)]);
+
+        let map = linear_map! {
+            String::from("x") => vec![1],
+            String::from("y") => vec![],
+        };
+        assert_eq!(map["x"], [1]);
+
+        let empty: LinearMap<u8, u8> = linear_map! {};
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn linear_map_duplicates() {
+        let map = linear_map! { 1 => "a", 2 => "b", 1 => "c" };
+        assert_eq!(map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(), vec![(1, "c"), (2, "b")]);
+    }
+
+    #[test]
+    fn const_new() {
+        REGISTRY.lock().unwrap().insert(String::from("a"), 1);
+        assert_eq!(REGISTRY.lock().unwrap().get("a"), Some(&1));
+        assert!(SET.lock().unwrap().insert(7));
+    }
+
+    #[test]
+    fn static_linear_map() {
+        assert_eq!(STATUS.len(), 3);
+        assert_eq!(STATUS.get(&404), Some(&"Not Found"));
+        assert_eq!(STATUS.get_key_value(&500), Some((&500, &"Internal Server Error")));
+        assert_eq!(STATUS.get(&418), None);
+        assert!(STATUS.contains_key(&200));
+        assert_eq!(STATUS[&200], "OK");
+        assert_eq!(STATUS.keys().cloned().collect::<Vec<_>>(), vec![200, 404, 500]);
+        assert_eq!(STATUS.values().count(), 3);
+        assert_eq!(STATUS.iter().size_hint(), (3, Some(3)));
+        assert_eq!(STATUS.keys().skip(1).len(), 2);
+        assert_eq!(STATUS.values().len(), 3);
+        assert_eq!((&STATUS).into_iter().next(), Some((&200, &"OK")));
+        assert_eq!(STATUS.entries()[1], (404, "Not Found"));
+
+        assert!(BIG[&5_000_000_000]);
+        assert!(!BIG[&0]);
+        assert_eq!(NEGATIVE[&-1], 'a');
+        assert_eq!(FLAGS[&false], "no");
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY, StaticLinearMap::default());
+        assert_eq!(PATHS[&2], "two");
+
+        let local = static_linear_map! { "b" => 2, "a" => 1 };
+        assert_eq!(local, static_linear_map! { "a" => 1, "b" => 2 });
+        assert_ne!(local, static_linear_map! { "a" => 1, "b" => 3 });
+        assert_eq!(format!("{:?}", local), r#"{"b": 2, "a": 1}"#);
+    }
+
+    #[test]
+    #[should_panic(expected = "no entry found for key")]
+    fn static_linear_map_index_missing() {
+        let _ = STATUS[&418];
+    }
+
+    #[test]
+    fn static_linear_map_duplicates() {
+        assert_eq!(DUPLICATES.len(), 2);
+        assert_eq!(DUPLICATES[&1], "first");
+        assert_eq!(DUPLICATES.values().cloned().collect::<Vec<_>>(), vec!["first", "second"]);
+
+        let map = DUPLICATES.to_linear_map();
+        assert_eq!(map.len(), 1);
+        assert_eq!(map[&1], "first");
+        assert_eq!(STATUS.to_linear_map(), linear_map! { 500 => "Internal Server Error", 200 => "OK", 404 => "Not Found" });
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod view {
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+    use {LinearMap, LinearMapMut, LinearMapRef};
+
+    fn check(view: LinearMapRef<String, usize>, n: usize) {
+        assert_eq!(view.len(), n);
+        assert_eq!(view.is_empty(), n == 0);
+        for i in 0..n {
+            assert_eq!(view.get(&*i.to_string()), Some(&i));
+            assert_eq!(view[&i.to_string()], i);
+            assert!(view.contains_key(&*i.to_string()));
+        }
+        assert_eq!(view.get("x"), None);
+        assert_eq!(view.get_key_value("0").map(|(k, v)| (k.as_str(), *v)), if n > 0 { Some(("0", 0)) } else { None });
+        assert!(view.iter().all(|(k, v)| *k == v.to_string()));
+        assert!(view.keys().eq(view.iter().map(|(k, _)| k)));
+        assert!(view.values().eq(view.iter().map(|(_, v)| v)));
+    }
+
+    #[test]
+    fn layouts() {
+        for &n in &[0, 1, 5] {
+            let map: LinearMap<String, usize> = (0..n).map(|i| (i.to_string(), i)).collect();
+            let keys: Vec<String> = map.keys().cloned().collect();
+            let values: Vec<usize> = map.values().cloned().collect();
+            let pairs: Vec<(String, usize)> = map.clone().into_iter().collect();
+
+            check(map.as_view(), n);
+            check(LinearMapRef::new(&keys, &values), n);
+            check(LinearMapRef::from_pairs(&pairs), n);
+            check(LinearMapRef::from(&pairs[..]), n);
+            assert_eq!(map.as_view(), LinearMapRef::from_pairs(&pairs));
+            assert_eq!(format!("{:?}", map.as_view()), format!("{:?}", map));
+        }
+        assert!(LinearMapRef::<u8, u8>::default().is_empty());
+    }
+
+    #[test]
+    fn view_outlives_borrow() {
+        let keys = [1, 2, 3];
+        let values = ["a", "b", "c"];
+        let value = {
+            let view = LinearMapRef::new(&keys, &values);
+            view.get(&2)
+        };
+        assert_eq!(value, Some(&"b"));
+        let view = LinearMapRef::new(&keys, &values);
+        assert_eq!(view.into_iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(), vec![(1, "a"), (2, "b"), (3, "c")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "different lengths")]
+    fn lengths_differ() {
+        LinearMapRef::new(&[1, 2], &[1]);
+    }
+
+    #[test]
+    fn duplicates() {
+        let pairs = [(1, "a"), (2, "b"), (1, "c")];
+        let view = LinearMapRef::from_pairs(&pairs);
+        assert_eq!(view.get(&1), Some(&"a"));
+        assert_eq!(view.len(), 3);
+        assert_eq!(view.values().count(), 3);
+    }
+
+    #[test]
+    fn mutable() {
+        let mut map = LinearMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        {
+            let mut view = map.as_view_mut();
+            *view.get_mut("a").unwrap() += 10;
+            assert_eq!(view.get_mut("c"), None);
+            for v in view.values_mut() {
+                *v *= 2;
+            }
+            for (_, v) in &mut view {
+                *v += 1;
+            }
+            assert_eq!(view["a"], 23);
+            assert_eq!(view.len(), 2);
+            assert!(view.contains_key("b"));
+            assert_eq!(view.get_key_value("b"), Some((&"b", &5)));
+            assert_eq!((&view).into_iter().count(), 2);
+            assert!(view.keys().eq(&["a", "b"]));
+            assert_eq!(view.as_view(), LinearMapRef::from_pairs(&[("b", 5), ("a", 23)]).clone());
+        }
+        assert_eq!(map["a"], 23);
+
+        let mut pairs = [("x", 1), ("y", 2)];
+        {
+            let mut view = LinearMapMut::from_pairs(&mut pairs);
+            *view.get_mut("y").unwrap() = 20;
+            for (k, v) in view.iter_mut() {
+                if *k == "x" {
+                    *v = 10;
+                }
+            }
+            assert!(view.values().eq(&[10, 20]));
+            for (_, v) in view {
+                *v += 1;
+            }
+        }
+        assert_eq!(pairs, [("x", 11), ("y", 21)]);
+
+        let keys = [1, 2];
+        let mut values = [0, 0];
+        let view: LinearMapRef<_, _> = {
+            let mut view = LinearMapMut::new(&keys, &mut values);
+            *view.get_mut(&2).unwrap() = 5;
+            view.into()
+        };
+        assert_eq!(view.get(&2), Some(&5));
+        assert_eq!(format!("{:?}", LinearMapMut::from(&mut [(1, 2)][..])), "{1: 2}");
+    }
+
+    #[test]
+    fn static_map() {
+        let map = static_linear_map! { "a" => 1, "b" => 2 };
+        assert_eq!(map.as_view().get("b"), Some(&2));
+        assert_eq!(map.as_view().len(), 2);
+    }
 }
@@ -0,0 +1,590 @@
+//! Borrowed views that search existing slices of keys and values like a
+//! [`LinearMap`](../struct.LinearMap.html), without copying them into a map first.
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::ops::Index;
+use core::slice;
+
+use {find, Iter as ColumnsIter, IterMut as ColumnsIterMut};
+
+/// The slices of a view: either parallel slices of keys and values, or a slice of pairs.
+enum Slices<'a, K: 'a, V: 'a> {
+    Columns(&'a [K], &'a [V]),
+    Pairs(&'a [(K, V)]),
+}
+
+impl<'a, K, V> Clone for Slices<'a, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Copy for Slices<'a, K, V> {}
+
+enum SlicesMut<'a, K: 'a, V: 'a> {
+    Columns(&'a [K], &'a mut [V]),
+    Pairs(&'a mut [(K, V)]),
+}
+
+/// Finds the position of `key` in a slice of pairs, like `find` does for a slice of keys.
+fn find_pair<K, V, Q>(pairs: &[(K, V)], key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+{
+    pairs.iter().position(|(k, _)| key.eq(k.borrow()))
+}
+
+/// LinearMapRef is a read-only view that searches borrowed slices like a
+/// [`LinearMap`](struct.LinearMap.html), for data that already lives in parallel slices of
+/// keys and values or in a table of key-value pairs. Nothing is copied, and the entries are
+/// visited in the order of the slices.
+///
+/// A view of a `LinearMap` is returned by
+/// [`LinearMap::as_view`](struct.LinearMap.html#method.as_view). The slices are not checked
+/// for duplicate keys. If a key occurs more than once, the lookup methods return its first
+/// entry, while iterators visit all of them.
+///
+/// # Examples
+///
+/// ```
+/// extern crate linear_map;
+/// use linear_map::LinearMapRef;
+///
+/// let names = ["x", "y", "z"];
+/// let offsets = [0, 4, 8];
+/// let fields = LinearMapRef::new(&names, &offsets);
+/// assert_eq!(fields.get("y"), Some(&4));
+///
+/// let table = [("width", 640), ("height", 480)];
+/// let size = LinearMapRef::from_pairs(&table);
+/// assert_eq!(size["height"], 480);
+/// ```
+pub struct LinearMapRef<'a, K: 'a, V: 'a> {
+    slices: Slices<'a, K, V>,
+}
+
+impl<'a, K, V> LinearMapRef<'a, K, V> {
+    /// Creates a view over parallel slices of keys and values, where `values[i]` belongs to
+    /// `keys[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    pub const fn new(keys: &'a [K], values: &'a [V]) -> Self {
+        assert!(keys.len() == values.len(), "keys and values of a LinearMapRef have different lengths");
+        LinearMapRef { slices: Slices::Columns(keys, values) }
+    }
+
+    /// Creates a view over a slice of key-value pairs.
+    pub const fn from_pairs(pairs: &'a [(K, V)]) -> Self {
+        LinearMapRef { slices: Slices::Pairs(pairs) }
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        match self.slices {
+            Slices::Columns(keys, _) => keys.len(),
+            Slices::Pairs(pairs) => pairs.len(),
+        }
+    }
+
+    /// Returns if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the requested value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'a V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Returns a tuple with references to the requested key and value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&'a K, &'a V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.slices {
+            Slices::Columns(keys, values) => find(keys, key).map(|i| (&keys[i], &values[i])),
+            Slices::Pairs(pairs) => find_pair(pairs, key).map(|i| (&pairs[i].0, &pairs[i].1)),
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.get_key_value(key).is_some()
+    }
+
+    /// Returns an iterator over the keys in the order of the slices.
+    pub fn keys(&self) -> Keys<'a, K, V> {
+        let inner = match self.slices {
+            Slices::Columns(keys, _) => Inner::Columns(keys.iter()),
+            Slices::Pairs(pairs) => Inner::Pairs(pairs.iter()),
+        };
+        Keys { inner }
+    }
+
+    /// Returns an iterator over the values in the order of the slices.
+    pub fn values(&self) -> Values<'a, K, V> {
+        let inner = match self.slices {
+            Slices::Columns(_, values) => Inner::Columns(values.iter()),
+            Slices::Pairs(pairs) => Inner::Pairs(pairs.iter()),
+        };
+        Values { inner }
+    }
+
+    /// Returns an iterator over the key-value pairs in the order of the slices.
+    pub fn iter(&self) -> Iter<'a, K, V> {
+        let inner = match self.slices {
+            Slices::Columns(keys, values) => Inner::Columns(ColumnsIter {
+                key: keys.iter(),
+                value: values.iter(),
+            }),
+            Slices::Pairs(pairs) => Inner::Pairs(pairs.iter()),
+        };
+        Iter { inner }
+    }
+}
+
+impl<'a, K, V> Clone for LinearMapRef<'a, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Copy for LinearMapRef<'a, K, V> {}
+
+impl<'a, K, V> Default for LinearMapRef<'a, K, V> {
+    fn default() -> Self {
+        LinearMapRef::from_pairs(&[])
+    }
+}
+
+impl<'a, K, V> fmt::Debug for LinearMapRef<'a, K, V>
+    where
+        K: fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// The order of the entries is ignored, and so is whether the views borrow separate key and
+/// value slices or a slice of pairs.
+impl<'a, 'b, K, V> PartialEq<LinearMapRef<'b, K, V>> for LinearMapRef<'a, K, V>
+    where
+        K: PartialEq,
+        V: PartialEq,
+{
+    fn eq(&self, other: &LinearMapRef<'b, K, V>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<'a, K, V> Eq for LinearMapRef<'a, K, V>
+    where
+        K: Eq,
+        V: Eq,
+{
+}
+
+impl<'a, K, V, Q> Index<&Q> for LinearMapRef<'a, K, V>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<'a, K, V> From<&'a [(K, V)]> for LinearMapRef<'a, K, V> {
+    fn from(pairs: &'a [(K, V)]) -> Self {
+        LinearMapRef::from_pairs(pairs)
+    }
+}
+
+impl<'a, K, V> IntoIterator for LinearMapRef<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &LinearMapRef<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+/// LinearMapMut is a view like [`LinearMapRef`](struct.LinearMapRef.html) that can also
+/// modify the values in the borrowed slices. The keys cannot be modified, and entries
+/// cannot be inserted or removed.
+///
+/// A view of a `LinearMap` is returned by
+/// [`LinearMap::as_view_mut`](struct.LinearMap.html#method.as_view_mut).
+///
+/// # Examples
+///
+/// ```
+/// extern crate linear_map;
+/// use linear_map::LinearMapMut;
+///
+/// let mut table = [("hits", 0), ("misses", 0)];
+/// let mut counters = LinearMapMut::from_pairs(&mut table);
+/// *counters.get_mut("hits").unwrap() += 1;
+/// assert_eq!(table, [("hits", 1), ("misses", 0)]);
+/// ```
+pub struct LinearMapMut<'a, K: 'a, V: 'a> {
+    slices: SlicesMut<'a, K, V>,
+}
+
+impl<'a, K, V> LinearMapMut<'a, K, V> {
+    /// Creates a view over parallel slices of keys and values, where `values[i]` belongs to
+    /// `keys[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    pub fn new(keys: &'a [K], values: &'a mut [V]) -> Self {
+        assert!(keys.len() == values.len(), "keys and values of a LinearMapMut have different lengths");
+        LinearMapMut { slices: SlicesMut::Columns(keys, values) }
+    }
+
+    /// Creates a view over a slice of key-value pairs.
+    pub fn from_pairs(pairs: &'a mut [(K, V)]) -> Self {
+        LinearMapMut { slices: SlicesMut::Pairs(pairs) }
+    }
+
+    /// Returns a read-only view of the same slices.
+    pub fn as_view(&self) -> LinearMapRef<'_, K, V> {
+        let slices = match self.slices {
+            SlicesMut::Columns(keys, ref values) => Slices::Columns(keys, values),
+            SlicesMut::Pairs(ref pairs) => Slices::Pairs(pairs),
+        };
+        LinearMapRef { slices }
+    }
+
+    /// Converts the view into a read-only view for the whole lifetime of the slices.
+    pub fn into_view(self) -> LinearMapRef<'a, K, V> {
+        let slices = match self.slices {
+            SlicesMut::Columns(keys, values) => Slices::Columns(keys, values),
+            SlicesMut::Pairs(pairs) => Slices::Pairs(pairs),
+        };
+        LinearMapRef { slices }
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the requested value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.as_view().get(key)
+    }
+
+    /// Returns a mutable reference to the requested value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        match self.slices {
+            SlicesMut::Columns(keys, ref mut values) => find(keys, key).map(move |i| &mut values[i]),
+            SlicesMut::Pairs(ref mut pairs) => find_pair(pairs, key).map(move |i| &mut pairs[i].1),
+        }
+    }
+
+    /// Returns a tuple with references to the requested key and value when available.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.as_view().get_key_value(key)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Time Complexity
+    ///
+    /// O(n) where n is the number of elements in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: PartialEq + ?Sized,
+    {
+        self.as_view().contains_key(key)
+    }
+
+    /// Returns an iterator over the keys in the order of the slices.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.as_view().keys()
+    }
+
+    /// Returns an iterator over the values in the order of the slices.
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.as_view().values()
+    }
+
+    /// Returns an iterator over mutable references to the values in the order of the slices.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        let inner = match self.slices {
+            SlicesMut::Columns(_, ref mut values) => Inner::Columns(values.iter_mut()),
+            SlicesMut::Pairs(ref mut pairs) => Inner::Pairs(pairs.iter_mut()),
+        };
+        ValuesMut { inner }
+    }
+
+    /// Returns an iterator over the key-value pairs in the order of the slices.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.as_view().iter()
+    }
+
+    /// Returns an iterator over the key-value pairs in the order of the slices, with mutable
+    /// references to the values.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        let inner = match self.slices {
+            SlicesMut::Columns(keys, ref mut values) => Inner::Columns(ColumnsIterMut {
+                key: keys.iter(),
+                value: values.iter_mut(),
+            }),
+            SlicesMut::Pairs(ref mut pairs) => Inner::Pairs(pairs.iter_mut()),
+        };
+        IterMut { inner }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for LinearMapMut<'a, K, V>
+    where
+        K: fmt::Debug,
+        V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V, Q> Index<&Q> for LinearMapMut<'a, K, V>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<'a, K, V> From<&'a mut [(K, V)]> for LinearMapMut<'a, K, V> {
+    fn from(pairs: &'a mut [(K, V)]) -> Self {
+        LinearMapMut::from_pairs(pairs)
+    }
+}
+
+impl<'a, K, V> From<LinearMapMut<'a, K, V>> for LinearMapRef<'a, K, V> {
+    fn from(view: LinearMapMut<'a, K, V>) -> Self {
+        view.into_view()
+    }
+}
+
+impl<'a, K, V> IntoIterator for LinearMapMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        let inner = match self.slices {
+            SlicesMut::Columns(keys, values) => Inner::Columns(ColumnsIterMut {
+                key: keys.iter(),
+                value: values.iter_mut(),
+            }),
+            SlicesMut::Pairs(pairs) => Inner::Pairs(pairs.iter_mut()),
+        };
+        IterMut { inner }
+    }
+}
+
+impl<'a, 'b, K, V> IntoIterator for &'b LinearMapMut<'a, K, V> {
+    type Item = (&'b K, &'b V);
+    type IntoIter = Iter<'b, K, V>;
+
+    fn into_iter(self) -> Iter<'b, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, 'b, K, V> IntoIterator for &'b mut LinearMapMut<'a, K, V> {
+    type Item = (&'b K, &'b mut V);
+    type IntoIter = IterMut<'b, K, V>;
+
+    fn into_iter(self) -> IterMut<'b, K, V> {
+        self.iter_mut()
+    }
+}
+
+/// The state of an iterator over a view, which depends on the layout of its slices.
+enum Inner<C, P> {
+    Columns(C),
+    Pairs(P),
+}
+
+/// An iterator over the entries of a LinearMapRef or LinearMapMut.
+///
+/// This struct is created by the `iter` methods on
+/// [`LinearMapRef`](../struct.LinearMapRef.html) and
+/// [`LinearMapMut`](../struct.LinearMapMut.html). See their documentation for more.
+pub struct Iter<'a, K: 'a, V: 'a> {
+    inner: Inner<ColumnsIter<'a, K, V>, slice::Iter<'a, (K, V)>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        match self.inner {
+            Inner::Columns(ref mut iter) => iter.next(),
+            Inner::Pairs(ref mut iter) => iter.next().map(|(k, v)| (k, v)),
+        }
+    }
+}
+
+/// A mutable iterator over the entries of a LinearMapMut.
+///
+/// This struct is created by the `iter_mut` method on
+/// [`LinearMapMut`](../struct.LinearMapMut.html). See its documentation for more.
+pub struct IterMut<'a, K: 'a, V: 'a> {
+    inner: Inner<ColumnsIterMut<'a, K, V>, slice::IterMut<'a, (K, V)>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a mut V)> {
+        match self.inner {
+            Inner::Columns(ref mut iter) => iter.next(),
+            Inner::Pairs(ref mut iter) => iter.next().map(|(k, v)| (&*k, v)),
+        }
+    }
+}
+
+/// An iterator over the keys of a LinearMapRef or LinearMapMut.
+///
+/// This struct is created by the `keys` methods on
+/// [`LinearMapRef`](../struct.LinearMapRef.html) and
+/// [`LinearMapMut`](../struct.LinearMapMut.html). See their documentation for more.
+pub struct Keys<'a, K: 'a, V: 'a> {
+    inner: Inner<slice::Iter<'a, K>, slice::Iter<'a, (K, V)>>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        match self.inner {
+            Inner::Columns(ref mut iter) => iter.next(),
+            Inner::Pairs(ref mut iter) => iter.next().map(|(k, _)| k),
+        }
+    }
+}
+
+/// An iterator over the values of a LinearMapRef or LinearMapMut.
+///
+/// This struct is created by the `values` methods on
+/// [`LinearMapRef`](../struct.LinearMapRef.html) and
+/// [`LinearMapMut`](../struct.LinearMapMut.html). See their documentation for more.
+pub struct Values<'a, K: 'a, V: 'a> {
+    inner: Inner<slice::Iter<'a, V>, slice::Iter<'a, (K, V)>>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        match self.inner {
+            Inner::Columns(ref mut iter) => iter.next(),
+            Inner::Pairs(ref mut iter) => iter.next().map(|(_, v)| v),
+        }
+    }
+}
+
+/// A mutable iterator over the values of a LinearMapMut.
+///
+/// This struct is created by the `values_mut` method on
+/// [`LinearMapMut`](../struct.LinearMapMut.html). See its documentation for more.
+pub struct ValuesMut<'a, K: 'a, V: 'a> {
+    inner: Inner<slice::IterMut<'a, V>, slice::IterMut<'a, (K, V)>>,
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<&'a mut V> {
+        match self.inner {
+            Inner::Columns(ref mut iter) => iter.next(),
+            Inner::Pairs(ref mut iter) => iter.next().map(|(_, v)| v),
+        }
+    }
+}